@@ -14,12 +14,18 @@
  limitations under the License.
  */
 
+use std::convert::TryFrom;
+use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
 
 use base64;
 
 /// Base64URL are Strings restricted to containing the 2^6 UTF-8 code points in the Base64URL bytes-to-characters encoding.
 /// Base64URL MUST NOT contain padding. 
+/// 
+/// The inner String is private, so a Base64URL can only be created by [Base64URL::encode], or by parsing a string that
+/// [Base64URL::decode] accepts. A Base64URL is therefore always valid Base64URL.
 pub struct Base64URL(String);
 
 
@@ -44,3 +50,36 @@ impl Deref for Base64URL {
         &self.0
     }
 }
+
+impl fmt::Display for Base64URL {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Base64URL {
+    type Err = base64::DecodeError;
+
+    /// Parse `s` as a Base64URL, returning a base64::DecodeError if [Base64URL::decode] rejects it, or if `s` is not exactly
+    /// how [Base64URL::encode] encodes the decoded bytes. The latter rejects padding, which [Base64URL::decode] accepts, so that
+    /// each sequence of bytes has only one Base64URL.
+    fn from_str(s: &str) -> Result<Base64URL, base64::DecodeError> {
+        let encoded = Base64URL::encode(Base64URL::decode(s)?);
+        if *encoded != s {
+            let offset = s.bytes().zip(encoded.bytes()).position(|(a, b)| a != b).unwrap_or_else(|| encoded.len().min(s.len()));
+            return Err(match s.as_bytes().get(offset) {
+                Some(byte) => base64::DecodeError::InvalidByte(offset, *byte),
+                None => base64::DecodeError::InvalidLength,
+            })
+        }
+        Ok(encoded)
+    }
+}
+
+impl TryFrom<&str> for Base64URL {
+    type Error = base64::DecodeError;
+
+    fn try_from(s: &str) -> Result<Base64URL, base64::DecodeError> {
+        s.parse()
+    }
+}
@@ -18,6 +18,18 @@
 use crate::{Serializable, Deserializable};
 
 
+impl Serializable<bool> for bool {}
+
+impl Deserializable<bool> for bool {}
+
+impl Serializable<u8> for u8 {}
+
+impl Deserializable<u8> for u8 {}
+
+impl Serializable<u16> for u16 {}
+
+impl Deserializable<u16> for u16 {}
+
 impl Serializable<u32> for u32 {}
 
 impl Deserializable<u32> for u32 {}
@@ -26,9 +38,31 @@ impl Serializable<u64> for u64 {}
 
 impl Deserializable<u64> for u64 {}
 
-impl Serializable<Vec<u8>> for Vec<u8> {}
-  
-impl Deserializable<Vec<u8>> for Vec<u8> {}
+impl Serializable<u128> for u128 {}
+
+impl Deserializable<u128> for u128 {}
+
+impl Serializable<i32> for i32 {}
+
+impl Deserializable<i32> for i32 {}
+
+impl Serializable<i64> for i64 {}
+
+impl Deserializable<i64> for i64 {}
+
+impl Serializable<String> for String {}
+
+impl Deserializable<String> for String {}
+
+/// Fixed-size byte arrays, i.e., [crate::PublicAddress], [crate::Sha256Hash] and [crate::Signature] are serialized as their raw
+/// bytes without a length prefix. Deserialization fails unless the input is exactly the size of the array.
+impl Serializable<[u8; 32]> for [u8; 32] {}
+
+impl Deserializable<[u8; 32]> for [u8; 32] {}
+
+impl Serializable<[u8; 64]> for [u8; 64] {}
+
+impl Deserializable<[u8; 64]> for [u8; 64] {}
 
 impl<T: borsh::BorshSerialize> Serializable<Option<T>> for Option<T> where T: Serializable<T>{}
 
@@ -40,8 +74,8 @@ impl<T1 :borsh::BorshDeserialize, T2: borsh::BorshDeserialize, T3: borsh::BorshD
 impl<T1 :borsh::BorshSerialize,T2: borsh::BorshSerialize> Serializable<(T1,T2)> for (T1,T2) where T1: Serializable<T1>, T2: Serializable<T2> {}
 impl<T1 :borsh::BorshDeserialize,T2: borsh::BorshDeserialize> Deserializable<(T1,T2)> for (T1,T2) where T1: Deserializable<T1>, T2: Deserializable<T2> {}
 
-/// Implementation of generic type in Vec. The serialization scheme follows Length-Value pattern.
+/// Implementation of generic type in Vec (including Vec<u8>). The serialization scheme follows Length-Value pattern.
 impl<T :borsh::BorshSerialize> Serializable<Vec<T>> for Vec<T> where T: Serializable<T>{}
 
-/// Implementation of generic type in Vec. The serialization scheme follows Length-Value pattern.
+/// Implementation of generic type in Vec (including Vec<u8>). The serialization scheme follows Length-Value pattern.
 impl<T :borsh::BorshDeserialize> Deserializable<Vec<T>> for Vec<T> where T: Deserializable<T> {}
\ No newline at end of file
@@ -14,13 +14,22 @@
  limitations under the License.
  */
 
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, HashMap};
 use std::convert::{TryFrom, TryInto};
-use crate::{crypto, Transaction, Receipt, Serializable, Deserializable};
+use sha2::{Sha256, Digest};
+use crate::{crypto, Transaction, Receipt, Event, EventFilter, ReceiptStatusCode, DeserializationContext, MerkleProof, CryptographicallyIncorrectTransactionError, Serializable, Deserializable};
 
 pub const BLOCK_GAS_LIMIT: usize = 67_500_000;
 pub const BLOCK_SIZE_LIMIT: usize = 1_048_576;
 
+/// A block consists of a header and, for each of its transactions, the receipt at the same index in `receipts`.
+/// 
+/// The fields are public, but a well-formed block has exactly as many receipts as transactions. Construct blocks with
+/// [Block::try_new], and check blocks received from peers with [Block::validate_structure]. Note that `Block::serialize` does not check
+/// this, and happily serializes a block that is not well-formed: serialize blocks to be sent to peers with [Block::try_serialize].
 #[derive(borsh::BorshSerialize, borsh::BorshDeserialize, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Block {
     pub header : BlockHeader,
     pub transactions : Vec<Transaction>,
@@ -29,35 +38,77 @@ pub struct Block {
 
 /// Block header defines meta information of a block, including evidence for verifying validity of the block.
 #[derive(Clone, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockHeader {
     /// Id of the blockchain. See [hotstuff_rs_types::messages::AppID]
     pub app_id: hotstuff_rs_types::messages::AppID,
     /// Block hash of this block
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub hash: crypto::Sha256Hash,
     /// Identifier for a height of a block on the blockchain encoded as a number. Block number starts with 0. 
     /// For any other case, it is incremented by 1 over the block number of the previous block
     pub height: u64, 
     /// A cryptographic certificate which links a Block with its direct ancestor. See [hotstuff_rs_types::messages::QuorumCertificate]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::borsh_as_base64url"))]
     pub justify: hotstuff_rs_types::messages::QuorumCertificate,
     /// A cryptographic hash over the Block's Data. See [hotstuff_rs_types::messages::DataHash]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub data_hash: hotstuff_rs_types::messages::DataHash,
     /// Identifier for the set of block validation rules for the blockchain
     pub version_number :u64,
     /// Unix timestamp
     pub timestamp: u32,
     /// Merkle Tree root hash of transactions
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub txs_hash : crypto::Sha256Hash,
     /// Merkle Tree root hash of current world-state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub state_hash : crypto::Sha256Hash,
     /// Merkle Tree root hash of receipts
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub receipts_hash : crypto::Sha256Hash,
 }
 
+impl BlockHeader {
+    /// The preimage of the block hash, as HotStuff computes it: the Borsh serializations of `app_id`, `height`, `justify` and
+    /// `data_hash`, in that order. The other fields are committed to through `data_hash` (see [Block::data_slots]).
+    pub fn hash_preimage(&self) -> Vec<u8> {
+        let mut preimage = Vec::new();
+        borsh::BorshSerialize::serialize(&self.app_id, &mut preimage).unwrap();
+        borsh::BorshSerialize::serialize(&self.height, &mut preimage).unwrap();
+        borsh::BorshSerialize::serialize(&self.justify, &mut preimage).unwrap();
+        borsh::BorshSerialize::serialize(&self.data_hash, &mut preimage).unwrap();
+        preimage
+    }
+
+    /// Compute the block hash of this header, i.e., the SHA256 hash of [BlockHeader::hash_preimage]. A header is consistent if
+    /// this equals its `hash`.
+    pub fn compute_hash(&self) -> crypto::Sha256Hash {
+        Sha256::digest(self.hash_preimage()).into()
+    }
+}
+
 impl Serializable<Block> for Block {}
 impl Deserializable<Block> for Block {}
 impl Serializable<BlockHeader> for BlockHeader {}
 impl Deserializable<BlockHeader> for BlockHeader {}
 
+impl TryFrom<&[u8]> for Block {
+    type Error = std::io::Error;
+
+    fn try_from(buf: &[u8]) -> Result<Block, std::io::Error> {
+        Block::deserialize(buf)
+    }
+}
+
+impl TryFrom<&[u8]> for BlockHeader {
+    type Error = std::io::Error;
+
+    fn try_from(buf: &[u8]) -> Result<BlockHeader, std::io::Error> {
+        BlockHeader::deserialize(buf)
+    }
+}
+
 // Slot indexes definitions for
 // pchain_types::Block and hotstuff_rs::msg_types::Block interoperability
 impl Block {
@@ -69,6 +120,450 @@ impl Block {
     pub const RECEIPTS_HASH_SLOT: usize = 4;
 }
 
+impl Block {
+    /// Create a Block, checking that it is well-formed (see [Block::validate_structure]).
+    pub fn try_new(header: BlockHeader, transactions: Vec<Transaction>, receipts: Vec<Receipt>) -> Result<Block, BlockError> {
+        let block = Block { header, transactions, receipts };
+        block.validate_structure()?;
+        Ok(block)
+    }
+
+    /// Serialize this block, returning an error instead if it is not well-formed (see [Block::validate_structure]).
+    /// 
+    /// This shadows [Serializable::try_serialize] for `Block::try_serialize(&block)` calls. Call the trait method as
+    /// `<Block as Serializable<Block>>::try_serialize` to skip the structure check.
+    pub fn try_serialize(&self) -> Result<Vec<u8>, BlockError> {
+        self.validate_structure()?;
+        <Block as Serializable<Block>>::try_serialize(self).map_err(BlockError::SerializationFailed)
+    }
+
+    /// Deserialize a block and check that it is well-formed (see [Block::validate_structure]) and that its header's `txs_hash`
+    /// and `receipts_hash` match its transactions and receipts (see [Block::verify_content_hashes]).
+    pub fn deserialize_verified(buf: &[u8]) -> Result<Block, BlockError> {
+        let block = Block::deserialize(buf).map_err(BlockError::DeserializationFailed)?;
+        block.validate_structure()?;
+        block.verify_content_hashes().map_err(BlockError::ContentMismatch)?;
+        Ok(block)
+    }
+
+    /// Serialize this block followed by the 32-byte SHA256 hash of its serialization, as a checksum for storage. Blocks are
+    /// never sent to peers in this format.
+    pub fn serialize_with_checksum(&self) -> Vec<u8> {
+        let mut buf = Block::serialize(self);
+        let checksum = Sha256::digest(&buf);
+        buf.extend_from_slice(&checksum);
+        buf
+    }
+
+    /// Deserialize a block serialized by [Block::serialize_with_checksum]. Returns [BlockError::ChecksumMismatch] if the
+    /// checksum does not match, before attempting to deserialize the block, so that corruption can be told apart from a block
+    /// that is not correctly serialized ([BlockError::DeserializationFailed]).
+    pub fn deserialize_with_checksum(buf: &[u8]) -> Result<Block, BlockError> {
+        if buf.len() < 32 {
+            return Err(BlockError::DeserializationFailed(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected end of checksum")))
+        }
+        let (payload, checksum) = buf.split_at(buf.len() - 32);
+        if !crypto::ct_eq(&Sha256::digest(payload), checksum) {
+            return Err(BlockError::ChecksumMismatch)
+        }
+        Block::deserialize(payload).map_err(BlockError::DeserializationFailed)
+    }
+
+    /// Check that this block is well-formed, i.e., that there is exactly one receipt for each transaction.
+    pub fn validate_structure(&self) -> Result<(), BlockError> {
+        if self.transactions.len() != self.receipts.len() {
+            return Err(BlockError::ReceiptsLengthMismatch(ReceiptsLengthMismatch {
+                transactions: self.transactions.len(),
+                receipts: self.receipts.len(),
+            }))
+        }
+        Ok(())
+    }
+
+    /// Iterate over the transactions in this block together with their receipts, i.e., the receipts at the same index.
+    /// If the block has more transactions than receipts or vice versa (see [Block::try_new]), the extra items are not visited.
+    pub fn iter_with_receipts(&self) -> impl Iterator<Item = (&Transaction, &Receipt)> {
+        self.transactions.iter().zip(self.receipts.iter())
+    }
+
+    /// Iterate over the events emitted by this block's transactions in emission order, i.e., receipt by receipt, and within each
+    /// receipt in the order of its `events`. Each event is paired with the index of the receipt (and so transaction) that it is in.
+    pub fn iter_events(&self) -> impl Iterator<Item = (usize, &Event)> {
+        self.receipts.iter()
+            .enumerate()
+            .flat_map(|(i, receipt)| receipt.events.iter().map(move |event| (i, event)))
+    }
+
+    /// The events emitted by this block's transactions that are selected by `filter`, in emission order, each paired with the
+    /// index of its receipt. See [Block::iter_events].
+    pub fn filter_events(&self, filter: &EventFilter) -> Vec<(usize, &Event)> {
+        self.iter_events()
+            .filter(|(_, event)| filter.matches(event))
+            .collect()
+    }
+
+    /// The transactions in this block sent by `sender`, in block order, each paired with its receipt.
+    pub fn sender_activity<'a>(&'a self, sender: &crypto::PublicAddress) -> Vec<(&'a Transaction, &'a Receipt)> {
+        self.iter_with_receipts()
+            .filter(|(txn, _)| txn.from_address == *sender)
+            .collect()
+    }
+
+    /// Total gas consumed by this block's transactions, as recorded in its receipts. The sum saturates at u64::MAX
+    /// instead of overflowing.
+    pub fn total_gas_consumed(&self) -> u64 {
+        self.receipts.iter()
+            .fold(0u64, |total, receipt| total.saturating_add(receipt.gas_consumed))
+    }
+
+    /// Check that the `from_address` of every transaction in this block is a valid Ed25519 public key. This is much cheaper
+    /// than verifying signatures, so it can be used to reject a block early. Returns the index of the first transaction
+    /// with an invalid `from_address`.
+    pub fn all_senders_valid(&self) -> Result<(), usize> {
+        match self.transactions.iter().position(|txn| ed25519_dalek::PublicKey::from_bytes(&txn.from_address).is_err()) {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that, for each sender, the nonces (`n_txs_on_chain_from_address`) of its transactions in this block are strictly
+    /// increasing in block order. Returns the first transaction that breaks its sender's sequence.
+    pub fn verify_nonce_sequences(&self) -> Result<(), NonceError> {
+        let mut last_nonces: HashMap<crypto::PublicAddress, u64> = HashMap::new();
+        for (index, txn) in self.transactions.iter().enumerate() {
+            if let Some(last_nonce) = last_nonces.insert(txn.from_address, txn.n_txs_on_chain_from_address) {
+                if txn.n_txs_on_chain_from_address <= last_nonce {
+                    return Err(NonceError { from_address: txn.from_address, index })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Net change in the balance of `address` caused by the transactions in this block. Only transactions whose receipt
+    /// (the receipt at the same index) is successful are counted. For each such transaction:
+    /// - if `address` is its `to_address`, `value` is added;
+    /// - if `address` is its `from_address`, `value` and the fees, i.e., `gas_consumed * gas_price` and `tip`, are subtracted.
+    /// 
+    /// The delta is computed in i128 so that it does not underflow, and saturates on a malformed block whose gas fees do not
+    /// fit in it.
+    pub fn balance_delta_for(&self, address: &crypto::PublicAddress) -> i128 {
+        self.iter_with_receipts()
+            .filter(|(_, receipt)| receipt.is_success())
+            .fold(0i128, |delta, (txn, receipt)| {
+                let mut delta = delta;
+                if txn.to_address == *address {
+                    delta = delta.saturating_add(txn.value as i128);
+                }
+                if txn.from_address == *address {
+                    let fees = (receipt.gas_consumed as i128).saturating_mul(txn.gas_price as i128).saturating_add(txn.tip as i128);
+                    delta = delta.saturating_sub(txn.value as i128).saturating_sub(fees);
+                }
+                delta
+            })
+    }
+
+    /// Aggregate the receipts of this block into a [BlockReceiptsResult] in a single pass. Returns None if a sum overflows,
+    /// which can only happen on a malformed block.
+    pub fn block_receipts_result(&self) -> Option<BlockReceiptsResult> {
+        self.receipts.iter().try_fold(BlockReceiptsResult::default(), |result, receipt| {
+            let (success_count, failure_count) = if receipt.is_success() {
+                (result.success_count.checked_add(1)?, result.failure_count)
+            } else {
+                (result.success_count, result.failure_count.checked_add(1)?)
+            };
+            Some(BlockReceiptsResult {
+                total_gas_consumed: result.total_gas_consumed.checked_add(receipt.gas_consumed)?,
+                success_count,
+                failure_count,
+                total_events: result.total_events.checked_add(receipt.events.len())?,
+            })
+        })
+    }
+
+    /// Check that the status codes of all receipts in `serialized_block`, a serialized Block, are valid. The transactions are
+    /// skipped over without being deserialized. Returns an error if a status code is invalid or if `serialized_block` is
+    /// not a valid serialization of a Block.
+    pub fn verify_receipt_codes(serialized_block: &[u8]) -> Result<(), std::io::Error> {
+        let offset = |buf: &[u8]| serialized_block.len() - buf.len();
+        let (_, mut buf) = split_transactions(serialized_block)?;
+
+        let start = offset(buf);
+        let num_receipts = <u32 as borsh::BorshDeserialize>::deserialize(&mut buf)
+            .map_err(|e| DeserializationContext::wrap(e, "number of receipts", start))?;
+        for _ in 0..num_receipts {
+            let start = offset(buf);
+            let status_code = <u8 as borsh::BorshDeserialize>::deserialize(&mut buf)
+                .map_err(|e| DeserializationContext::wrap(e, "receipt status code", start))?;
+            if ReceiptStatusCode::try_from(status_code).is_err() {
+                let message = format!("Invalid receipt status code {}", status_code);
+                return Err(DeserializationContext::error(std::io::ErrorKind::InvalidData, "receipt status code", start, &message))
+            }
+            let read_rest = |buf: &mut &[u8]| -> Result<(), std::io::Error> {
+                <u64 as borsh::BorshDeserialize>::deserialize(buf)?;
+                <Vec<u8> as borsh::BorshDeserialize>::deserialize(buf)?;
+                <Vec<Event> as borsh::BorshDeserialize>::deserialize(buf)?;
+                Ok(())
+            };
+            read_rest(&mut buf).map_err(|e| DeserializationContext::wrap(e, "receipt", start))?;
+        }
+
+        if !buf.is_empty() {
+            return Err(DeserializationContext::error(std::io::ErrorKind::InvalidData, "block", offset(buf), "Not all bytes read"))
+        }
+        Ok(())
+    }
+
+    /// Check that `header.txs_hash` and `header.receipts_hash` are the Merkle roots over this block's transactions and receipts,
+    /// as computed by [crypto::merkle_root].
+    pub fn verify_content_hashes(&self) -> Result<(), BlockContentError> {
+        if crypto::merkle_root::<Transaction, Transaction>(&self.transactions) != self.header.txs_hash {
+            return Err(BlockContentError::WrongTxsHash)
+        }
+        if crypto::merkle_root::<Receipt, Receipt>(&self.receipts) != self.header.receipts_hash {
+            return Err(BlockContentError::WrongReceiptsHash)
+        }
+        Ok(())
+    }
+
+    /// Build a single Merkle proof that the transactions at `indices` are included in this block, i.e., in the Merkle tree whose root is
+    /// `header.txs_hash`. The proof's `leaf_indices` are `indices` sorted in ascending order, without duplicates.
+    /// 
+    /// Returns None if any of `indices` is out of range of `transactions`.
+    pub fn merkle_multiproof(&self, indices: &[usize]) -> Option<MerkleProof> {
+        if indices.iter().any(|i| *i >= self.transactions.len()) {
+            return None
+        }
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let leaves: Vec<crypto::Sha256Hash> = self.transactions.iter()
+            .map(|txn| Sha256::digest(Transaction::serialize(txn)).into())
+            .collect();
+        Some(MerkleProof::build(&leaves, &indices))
+    }
+
+    /// Check that the total gas consumed by this block's transactions, as recorded in its receipts, does not
+    /// exceed [BLOCK_GAS_LIMIT].
+    pub fn verify_gas_limit(&self) -> Result<(), GasLimitExceeded> {
+        let total_gas_consumed = self.total_gas_consumed();
+        let limit = BLOCK_GAS_LIMIT as u64;
+        if total_gas_consumed > limit {
+            Err(GasLimitExceeded { total_gas_consumed, limit })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Block {
+    /// Size of this block when serialized. Only the header is serialized to compute it, so this is much cheaper than
+    /// serializing the whole block, and can be checked against [BLOCK_SIZE_LIMIT] before doing so.
+    pub fn serialized_size(&self) -> usize {
+        BlockHeader::serialize(&self.header).len()
+            + 4 + self.transactions.iter().map(Transaction::size_of).sum::<usize>()
+            + 4 + self.receipts.iter().map(Receipt::size_of).sum::<usize>()
+    }
+
+    /// All addresses that appear as the `from_address` or `to_address` of a transaction in this block, without duplicates.
+    pub fn touched_addresses(&self) -> BTreeSet<crypto::PublicAddress> {
+        self.transactions.iter()
+            .flat_map(|txn| [txn.from_address, txn.to_address])
+            .collect()
+    }
+
+    /// Iterate over the transactions in this block whose `gas_price` is at least `min`.
+    pub fn transactions_above_gas_price(&self, min: u64) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter().filter(move |txn| txn.gas_price >= min)
+    }
+
+    /// The data slots of the HotStuff block corresponding to this block, in the layout expected by
+    /// `TryFrom<hotstuff_rs_types::messages::Block>`: the [Block::NUM_SLOTS] header slots (version number and timestamp as
+    /// little-endian bytes, then `txs_hash`, `state_hash` and `receipts_hash`), followed by the serialized transactions, followed by
+    /// the serialized receipts.
+    pub fn data_slots(&self) -> Vec<Vec<u8>> {
+        let mut data = Vec::with_capacity(Block::NUM_SLOTS + self.transactions.len() + self.receipts.len());
+        data.push(self.header.version_number.to_le_bytes().to_vec());
+        data.push(self.header.timestamp.to_le_bytes().to_vec());
+        data.push(self.header.txs_hash.to_vec());
+        data.push(self.header.state_hash.to_vec());
+        data.push(self.header.receipts_hash.to_vec());
+        data.extend(self.transactions.iter().map(Transaction::serialize));
+        data.extend(self.receipts.iter().map(Receipt::serialize));
+        data
+    }
+}
+
+/// Blocks are ordered for chain walking: by `header.height`, then by `header.hash` to break ties between blocks at the same height
+/// (e.g., on competing forks). Two blocks are equal if they have the same height and hash, regardless of their other contents.
+impl PartialEq for Block {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Block {}
+
+impl PartialOrd for Block {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Block {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.header.height, self.header.hash).cmp(&(other.header.height, other.header.hash))
+    }
+}
+
+/// BlockHeaderMeta contains the fields of a [BlockHeader] which are not computed from the block's contents. See [BlockBuilder::new].
+#[derive(Clone)]
+pub struct BlockHeaderMeta {
+    /// Id of the blockchain. See [BlockHeader::app_id]
+    pub app_id: hotstuff_rs_types::messages::AppID,
+    /// Height of the block. See [BlockHeader::height]
+    pub height: u64,
+    /// Quorum certificate linking the block with its parent. See [BlockHeader::justify]
+    pub justify: hotstuff_rs_types::messages::QuorumCertificate,
+    /// Identifier for the set of block validation rules. See [BlockHeader::version_number]
+    pub version_number: u64,
+    /// Unix timestamp. See [BlockHeader::timestamp]
+    pub timestamp: u32,
+    /// Merkle Tree root hash of the world-state after executing the block. See [BlockHeader::state_hash]
+    pub state_hash: crypto::Sha256Hash,
+}
+
+/// BlockBuilder constructs a [Block] incrementally as transactions are executed. Transactions that would make the block exceed
+/// [BLOCK_SIZE_LIMIT] or [BLOCK_GAS_LIMIT] are rejected. The hashes in the header are computed once, in [BlockBuilder::finalize].
+pub struct BlockBuilder {
+    header: BlockHeader,
+    transactions: Vec<Transaction>,
+    receipts: Vec<Receipt>,
+    tentative_size: usize,
+    total_gas_consumed: u64,
+}
+
+impl BlockBuilder {
+    /// Start building an empty block with `header_meta`. The size of the header, which depends on `header_meta.justify`, counts
+    /// towards [BLOCK_SIZE_LIMIT] from the start.
+    pub fn new(header_meta: BlockHeaderMeta) -> BlockBuilder {
+        let header = BlockHeader {
+            app_id: header_meta.app_id,
+            hash: [0; 32],
+            height: header_meta.height,
+            justify: header_meta.justify,
+            data_hash: [0; 32],
+            version_number: header_meta.version_number,
+            timestamp: header_meta.timestamp,
+            txs_hash: [0; 32],
+            state_hash: header_meta.state_hash,
+            receipts_hash: [0; 32],
+        };
+        // The hashes are fixed-size, so the header has the same size once they are computed.
+        let tentative_size = BlockHeader::serialize(&header).len() + 4 + 4;
+        BlockBuilder { header, transactions: Vec::new(), receipts: Vec::new(), tentative_size, total_gas_consumed: 0 }
+    }
+
+    /// Append `tx` and its receipt to the block. Returns an error, leaving the block unchanged, if this would make the block
+    /// exceed [BLOCK_SIZE_LIMIT] or [BLOCK_GAS_LIMIT].
+    pub fn push_transaction(&mut self, tx: Transaction, receipt: Receipt) -> Result<(), BlockBuilderError> {
+        let tentative_size = self.tentative_size + tx.size_of() + receipt.size_of();
+        if tentative_size > BLOCK_SIZE_LIMIT {
+            return Err(BlockBuilderError::SizeLimitExceeded)
+        }
+        let total_gas_consumed = self.total_gas_consumed.saturating_add(receipt.gas_consumed);
+        if total_gas_consumed > BLOCK_GAS_LIMIT as u64 {
+            return Err(BlockBuilderError::GasLimitExceeded)
+        }
+
+        self.transactions.push(tx);
+        self.receipts.push(receipt);
+        self.tentative_size = tentative_size;
+        self.total_gas_consumed = total_gas_consumed;
+        Ok(())
+    }
+
+    /// Size of the block when serialized, including its header.
+    pub fn tentative_size(&self) -> usize {
+        self.tentative_size
+    }
+
+    /// Build the block, computing `txs_hash` and `receipts_hash` of its header, then its `data_hash` with `compute_data_hash`,
+    /// and then its `hash` (see [BlockHeader::compute_hash]). The data hash is computed by the node, so this crate does not
+    /// prescribe it. Returns an error if the block exceeds [BLOCK_SIZE_LIMIT], which is only possible if its header alone does.
+    pub fn finalize<F>(self, compute_data_hash: F) -> Result<Block, BlockBuilderError>
+    where F: FnOnce(&Block) -> hotstuff_rs_types::messages::DataHash {
+        let mut block = Block {
+            header: BlockHeader {
+                txs_hash: crypto::merkle_root::<Transaction, Transaction>(&self.transactions),
+                receipts_hash: crypto::merkle_root::<Receipt, Receipt>(&self.receipts),
+                ..self.header
+            },
+            transactions: self.transactions,
+            receipts: self.receipts,
+        };
+        if block.serialized_size() > BLOCK_SIZE_LIMIT {
+            return Err(BlockBuilderError::SizeLimitExceeded)
+        }
+        block.header.data_hash = compute_data_hash(&block);
+        block.header.hash = block.header.compute_hash();
+        Ok(block)
+    }
+}
+
+/// Check that `tx` is included in the block with header `header`, using `proof`: `proof` must be a proof over the transactions
+/// of that block (i.e., its `root_hash` is `header.txs_hash`), one of its leaves must be the SHA256 hash of the serialized `tx`,
+/// and it must verify.
+pub fn verify_transaction_inclusion(tx: &Transaction, proof: &MerkleProof, header: &BlockHeader) -> bool {
+    let leaf_hash: crypto::Sha256Hash = Sha256::digest(Transaction::serialize(tx)).into();
+    proof.root_hash == header.txs_hash
+        && proof.leaf_hashes.contains(&leaf_hash)
+        && proof.verify()
+}
+
+/// Iterate over the serialized transactions in `block_buf`, a serialized Block, without deserializing them. Only the header and
+/// the sizes of the transactions are parsed. Returns an error if `block_buf` is too short to contain all of its transactions.
+pub fn iter_transaction_bytes(block_buf: &[u8]) -> Result<impl Iterator<Item = &[u8]>, std::io::Error> {
+    let (transactions, _) = split_transactions(block_buf)?;
+    Ok(transactions.into_iter())
+}
+
+/// Split the serialized transactions in `serialized_block` off one by one, using [Transaction::size_from_slice]. Returns the
+/// slices of the transactions, and the bytes following them (i.e., the serialized receipts).
+fn split_transactions(serialized_block: &[u8]) -> Result<(Vec<&[u8]>, &[u8]), std::io::Error> {
+    let offset = |buf: &[u8]| serialized_block.len() - buf.len();
+    let mut buf = serialized_block;
+    <BlockHeader as borsh::BorshDeserialize>::deserialize(&mut buf)
+        .map_err(|e| DeserializationContext::wrap(e, "block header", 0))?;
+
+    let start = offset(buf);
+    let num_transactions = <u32 as borsh::BorshDeserialize>::deserialize(&mut buf)
+        .map_err(|e| DeserializationContext::wrap(e, "number of transactions", start))?;
+    let mut transactions = Vec::new();
+    for _ in 0..num_transactions {
+        let start = offset(buf);
+        let size = Transaction::size_from_slice(buf)
+            .map_err(|e| DeserializationContext::wrap(e, "transaction", start))?;
+        let (transaction, rest) = buf.split_at(size);
+        transactions.push(transaction);
+        buf = rest;
+    }
+    Ok((transactions, buf))
+}
+
+/// Sort `txs` into the canonical order of transactions in a block, so that proposers with the same transactions build identical
+/// blocks. Transactions are ordered by `gas_price`, highest first, then by `from_address`, lowest first, then by
+/// `n_txs_on_chain_from_address` (nonce), lowest first.
+pub fn canonical_transaction_order(txs: &mut [Transaction]) {
+    txs.sort_by_key(|txn| (Reverse(txn.gas_price), txn.from_address, txn.n_txs_on_chain_from_address));
+}
+
+/// Convert a HotStuff block with the data slot layout of [Block::data_slots] into a Block.
+/// 
+/// Every transaction is checked with [Transaction::verify_cryptographic_correctness] during the conversion, i.e., its hash is
+/// recomputed and its Ed25519 signature is verified. This dominates the cost of converting a block, and a block with any wrongly
+/// authenticated transaction fails to convert with [TryFromHotStuffBlockError::WronglyAuthenticatedTransaction].
 impl TryFrom<hotstuff_rs_types::messages::Block> for Block {
     type Error = TryFromHotStuffBlockError;
 
@@ -86,9 +581,9 @@ impl TryFrom<hotstuff_rs_types::messages::Block> for Block {
     
         let version_number: u64 =  u64::from_le_bytes(block.data[Block::VERSION_SLOT].as_slice().try_into().map_err(|_| TryFromHotStuffBlockError::WrongVersionNumberLength)?);
         let timestamp: u32 = u32::from_le_bytes(block.data[Block::TIMESTAMP_SLOT].as_slice().try_into().map_err(|_| TryFromHotStuffBlockError::WrongTimestampLength)?);
-        let txs_hash: crypto::Sha256Hash = block.data[Block::TXS_HASH_SLOT].as_slice().try_into().map_err(|_| TryFromHotStuffBlockError::WrongTxsHashLength)?;
-        let state_hash: crypto::Sha256Hash = block.data[Block::STATE_HASH_SLOT].as_slice().try_into().map_err(|_| TryFromHotStuffBlockError::WrongStateHashLength)?;
-        let receipts_hash: crypto::Sha256Hash = block.data[Block::RECEIPTS_HASH_SLOT].as_slice().try_into().map_err(|_| TryFromHotStuffBlockError::WrongReceiptsHashLength)?;
+        let txs_hash = crypto::sha256_hash_from_bytes(&block.data[Block::TXS_HASH_SLOT]).map_err(|_| TryFromHotStuffBlockError::WrongTxsHashLength)?;
+        let state_hash = crypto::sha256_hash_from_bytes(&block.data[Block::STATE_HASH_SLOT]).map_err(|_| TryFromHotStuffBlockError::WrongStateHashLength)?;
+        let receipts_hash = crypto::sha256_hash_from_bytes(&block.data[Block::RECEIPTS_HASH_SLOT]).map_err(|_| TryFromHotStuffBlockError::WrongReceiptsHashLength)?;
         
         let header: BlockHeader = BlockHeader {
             app_id,
@@ -118,7 +613,8 @@ impl TryFrom<hotstuff_rs_types::messages::Block> for Block {
             let mut transactions: Vec<Transaction> = Vec::with_capacity(txns_bs.len());
             for txn_bs in txns_bs {
                 let txn = Transaction::deserialize(txn_bs).map_err(|_| TryFromHotStuffBlockError::WronglySerializedTransaction)?;
-                transactions.push(txn.try_into().map_err(|e| TryFromHotStuffBlockError::WronglyAuthenticatedTransaction)?)
+                txn.verify_cryptographic_correctness().map_err(TryFromHotStuffBlockError::WronglyAuthenticatedTransaction)?;
+                transactions.push(txn)
             }
 
             let mut receipts = Vec::with_capacity(receipts_bs.len());
@@ -147,6 +643,83 @@ pub enum TryFromHotStuffBlockError {
     WrongStateHashLength,
     WrongReceiptsHashLength,
     WronglySerializedTransaction,
-    WronglyAuthenticatedTransaction,
+    /// A transaction failed [Transaction::verify_cryptographic_correctness], which is run on every transaction during the
+    /// conversion.
+    WronglyAuthenticatedTransaction(CryptographicallyIncorrectTransactionError),
     WrongReceipt,
 }
+
+/// GasLimitExceeded is returned when the gas consumed by a block's transactions exceeds the block gas limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasLimitExceeded {
+    /// Total gas consumed by the transactions in the block
+    pub total_gas_consumed: u64,
+    /// Maximum gas that the transactions in a block may consume. See [BLOCK_GAS_LIMIT]
+    pub limit: u64,
+}
+
+/// BlockReceiptsResult summarizes the receipts of a block. See [Block::block_receipts_result].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockReceiptsResult {
+    /// Total gas consumed by the transactions in the block
+    pub total_gas_consumed: u64,
+    /// Number of receipts with status code [crate::ReceiptStatusCode::Success]
+    pub success_count: usize,
+    /// Number of receipts with any other status code
+    pub failure_count: usize,
+    /// Total number of events emitted by the transactions in the block
+    pub total_events: usize,
+}
+
+/// NonceError is returned when a transaction in a block does not have a greater nonce than the previous transaction
+/// in the block from the same sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceError {
+    /// Sender of the offending transaction
+    pub from_address: crypto::PublicAddress,
+    /// Index of the offending transaction in the block
+    pub index: usize,
+}
+
+/// ReceiptsLengthMismatch is returned when a block does not have exactly one receipt for each transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptsLengthMismatch {
+    /// Number of transactions in the block
+    pub transactions: usize,
+    /// Number of receipts in the block
+    pub receipts: usize,
+}
+
+/// BlockError describes why a block is not well-formed, or could not be serialized or deserialized. See [Block::validate_structure].
+#[derive(Debug)]
+pub enum BlockError {
+    /// The block does not have exactly one receipt for each transaction.
+    ReceiptsLengthMismatch(ReceiptsLengthMismatch),
+    /// The block could not be serialized.
+    SerializationFailed(std::io::Error),
+    /// The block could not be deserialized.
+    DeserializationFailed(std::io::Error),
+    /// The hashes in the block's header do not match its contents.
+    ContentMismatch(BlockContentError),
+    /// The checksum of a block serialized with [Block::serialize_with_checksum] does not match, i.e., the block is corrupted.
+    ChecksumMismatch,
+}
+
+/// BlockContentError describes why the contents of a block do not match its header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockContentError {
+    /// `txs_hash` is not the Merkle root over the transactions.
+    WrongTxsHash,
+    /// `receipts_hash` is not the Merkle root over the receipts.
+    WrongReceiptsHash,
+}
+
+/// BlockBuilderError describes why a transaction cannot be appended to a [BlockBuilder], or the block cannot be finalized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockBuilderError {
+    /// The block would exceed [BLOCK_SIZE_LIMIT].
+    SizeLimitExceeded,
+    /// The block would exceed [BLOCK_GAS_LIMIT].
+    GasLimitExceeded,
+}
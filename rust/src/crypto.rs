@@ -15,8 +15,12 @@
  */
 
 use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+use ed25519_dalek::{Signer, Verifier};
 use rs_merkle::{Hasher, MerkleTree, algorithms::Sha256};
-use crate::Serializable;
+use subtle::ConstantTimeEq;
+use crate::{Base64URL, Serializable};
 
 /// An Ed25519 signature. These are generated by external accounts to authorize transactions,
 /// and by validators to create proposals and cast votes during consensus.
@@ -33,6 +37,91 @@ pub type PublicAddress = [u8; 32];
 /// A SHA256 hash. Used as block and transaction hashes, as well as to form Merkle tries.
 pub type Sha256Hash = [u8; 32];
 
+/// Base64URLAddress wraps a [PublicAddress] so that it is displayed as, and parsed from, a Base64URL string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64URLAddress(pub PublicAddress);
+
+/// Base64URLHash wraps a [Sha256Hash] so that it is displayed as, and parsed from, a Base64URL string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64URLHash(pub Sha256Hash);
+
+impl fmt::Display for Base64URLAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Base64URL::encode(self.0))
+    }
+}
+
+impl FromStr for Base64URLAddress {
+    type Err = ParseBase64URLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_base64url_array(s).map(Base64URLAddress)
+    }
+}
+
+impl fmt::Display for Base64URLHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Base64URL::encode(self.0))
+    }
+}
+
+impl FromStr for Base64URLHash {
+    type Err = ParseBase64URLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_base64url_array(s).map(Base64URLHash)
+    }
+}
+
+/// ParseBase64URLError is returned when a string cannot be parsed into a fixed-size cryptographic type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBase64URLError {
+    /// The string is not valid Base64URL.
+    InvalidBase64URL(base64::DecodeError),
+    /// The string decodes into the contained number of bytes, which is not the size of the type.
+    WrongLength(usize),
+}
+
+/// Parse a newline-delimited list of Base64URL [PublicAddress]es, e.g., a validator set or an allowlist file. Each line is trimmed
+/// and blank lines are skipped. Returns the 1-based line number and the error of the first line which is not a valid address.
+pub fn parse_address_list(input: &str) -> Result<Vec<PublicAddress>, (usize, ParseBase64URLError)> {
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_number, line)| line.parse::<Base64URLAddress>().map(|address| address.0).map_err(|e| (line_number, e)))
+        .collect()
+}
+
+/// Convert `bytes` into a [PublicAddress]. Returns an error if `bytes` is not 32 bytes long.
+pub fn public_address_from_bytes(bytes: &[u8]) -> Result<PublicAddress, IncorrectLengthError> {
+    array_from_bytes(bytes)
+}
+
+/// Convert `bytes` into a [Sha256Hash]. Returns an error if `bytes` is not 32 bytes long.
+pub fn sha256_hash_from_bytes(bytes: &[u8]) -> Result<Sha256Hash, IncorrectLengthError> {
+    array_from_bytes(bytes)
+}
+
+fn array_from_bytes<const N: usize>(bytes: &[u8]) -> Result<[u8; N], IncorrectLengthError> {
+    bytes.try_into().map_err(|_| IncorrectLengthError { expected: N, actual: bytes.len() })
+}
+
+/// IncorrectLengthError is returned when a slice of bytes cannot be converted into a fixed-size cryptographic type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncorrectLengthError {
+    /// The size of the type
+    pub expected: usize,
+    /// The length of the slice
+    pub actual: usize,
+}
+
+fn decode_base64url_array<const N: usize>(s: &str) -> Result<[u8; N], ParseBase64URLError> {
+    let bytes = Base64URL::decode(s).map_err(ParseBase64URLError::InvalidBase64URL)?;
+    let len = bytes.len();
+    bytes.try_into().map_err(|_| ParseBase64URLError::WrongLength(len))
+}
+
 // Computes the Merkle root hash of a vector of serializable data.
 pub fn merkle_root<A: Clone + Into<B>, B: Serializable<B>  + borsh::BorshSerialize>(data: &Vec<A>) -> Sha256Hash {
     // TODO [Alice]: null hash really isn't all 0s. 
@@ -74,6 +163,83 @@ pub fn merkle_proof<A: Clone + Into<B>, B: Serializable<B> + borsh::BorshSeriali
 
 pub struct LeafOutOfRangeError;
 
+/// Compute the SHA256 hash of the SHA256 hash of `bytes`, as used by Bitcoin. The protocol itself hashes once; this is for
+/// interoperating with external systems that expect double-SHA256, e.g., when anchoring block hashes onto another chain.
+pub fn double_sha256(bytes: &[u8]) -> Sha256Hash {
+    Sha256::hash(&Sha256::hash(bytes))
+}
+
+/// Shorten `addr` for display, e.g., in UIs: the first and last 4 characters of its Base64URL encoding, joined by an ellipsis.
+pub fn short_address(addr: &PublicAddress) -> String {
+    let encoded = Base64URL::encode(addr);
+    format!("{}…{}", &encoded[..4], &encoded[encoded.len() - 4..])
+}
+
+/// Sort public addresses into the canonical order of a validator set, i.e., lexicographically by their bytes.
+pub fn sort_addresses(addrs: &mut [PublicAddress]) {
+    addrs.sort_unstable();
+}
+
+/// Compute the SHA256 hash over the concatenation of the public addresses of a validator set, taken in canonical
+/// order (see [sort_addresses]). The result does not depend on the order of `addrs`.
+pub fn validator_set_hash(addrs: &[PublicAddress]) -> Sha256Hash {
+    let mut sorted_addrs = addrs.to_vec();
+    sort_addresses(&mut sorted_addrs);
+    Sha256::hash(&sorted_addrs.concat())
+}
+
+/// Sign each of `messages` with `keypair`, returning the signatures in the same order.
+pub fn sign_batch(keypair: &ed25519_dalek::Keypair, messages: &[&[u8]]) -> Vec<Signature> {
+    messages.iter().map(|message| keypair.sign(message).to_bytes()).collect()
+}
+
+/// Sign `message` with the Ed25519 keypair whose secret key is `secret`.
+pub fn sign(secret: &SecretKey, message: &[u8]) -> Signature {
+    // Any 32 bytes are a valid Ed25519 secret key, so this cannot fail.
+    let secret = ed25519_dalek::SecretKey::from_bytes(secret).unwrap();
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    ed25519_dalek::Keypair { secret, public }.sign(message).to_bytes()
+}
+
+/// Returns true if `sig` is a valid Ed25519 signature over `message` by `public`. Returns false if `public` or `sig` are
+/// malformed.
+pub fn verify(public: &PublicAddress, message: &[u8], sig: &Signature) -> bool {
+    let public = match ed25519_dalek::PublicKey::from_bytes(public) {
+        Ok(public) => public,
+        Err(_) => return false,
+    };
+    match ed25519_dalek::Signature::from_bytes(sig) {
+        Ok(sig) => public.verify(message, &sig).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Compare `a` and `b` in constant time, i.e., in time that depends only on their lengths and not on their contents.
+/// Use this instead of `==` to compare secret-derived values such as hashes and signatures.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Compare two [Sha256Hash]es in constant time. See [ct_eq].
+pub fn ct_eq_hash(a: &Sha256Hash, b: &Sha256Hash) -> bool {
+    ct_eq(a, b)
+}
+
+/// Compare two [Signature]s in constant time. See [ct_eq].
+pub fn ct_eq_signature(a: &Signature, b: &Signature) -> bool {
+    ct_eq(a, b)
+}
+
+/// Compute the address of the contract deployed by `deployer` in its Transaction with `n_txs_on_chain_from_address` equal to `nonce`.
+/// 
+/// The address is the SHA256 hash over the 32 bytes of `deployer` followed by the 8 bytes of `nonce` in little-endian.
+pub fn contract_address(deployer: &PublicAddress, nonce: u64) -> PublicAddress {
+    let mut preimage = Vec::with_capacity(40);
+    preimage.extend_from_slice(deployer);
+    preimage.extend_from_slice(&nonce.to_le_bytes());
+    Sha256::hash(&preimage)
+}
+
 fn sha256<C: Clone + Into<D>, D: Serializable<D> + borsh::BorshSerialize>(datum: &C) -> Sha256Hash {
         // TODO [Alice]: remove clone.
         let d: D = datum.clone().into();
@@ -0,0 +1,73 @@
+/*
+ Copyright 2022 ParallelChain Lab
+
+ Licensed under the Apache License, Version 2.0 (the "License");
+ you may not use this file except in compliance with the License.
+ You may obtain a copy of the License at
+
+     http://www.apache.org/licenses/LICENSE-2.0
+
+ Unless required by applicable law or agreed to in writing, software
+ distributed under the License is distributed on an "AS IS" BASIS,
+ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ See the License for the specific language governing permissions and
+ limitations under the License.
+ */
+
+use crate::{Block, Receipt, Transaction, Serializable, Deserializable};
+
+/// Envelope is a self-describing wrapper around a serialized protocol type, used to carry heterogeneous
+/// objects (e.g., in debug logs). `type_tag` identifies the type of `payload`.
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub struct Envelope {
+    /// Identifies the type of `payload`. One of the `*_TYPE_TAG` constants on Envelope.
+    pub type_tag: u8,
+    /// The serialized object.
+    pub payload: Vec<u8>,
+}
+
+impl Envelope {
+    pub const TRANSACTION_TYPE_TAG: u8 = 0;
+    pub const RECEIPT_TYPE_TAG: u8 = 1;
+    pub const BLOCK_TYPE_TAG: u8 = 2;
+
+    pub fn wrap_transaction(txn: &Transaction) -> Envelope {
+        Envelope { type_tag: Self::TRANSACTION_TYPE_TAG, payload: Transaction::serialize(txn) }
+    }
+
+    pub fn wrap_receipt(receipt: &Receipt) -> Envelope {
+        Envelope { type_tag: Self::RECEIPT_TYPE_TAG, payload: Receipt::serialize(receipt) }
+    }
+
+    pub fn wrap_block(block: &Block) -> Envelope {
+        Envelope { type_tag: Self::BLOCK_TYPE_TAG, payload: Block::serialize(block) }
+    }
+
+    /// Deserialize `payload` as a Transaction. Returns an error if `type_tag` is not [Envelope::TRANSACTION_TYPE_TAG].
+    pub fn unwrap_transaction(&self) -> Result<Transaction, std::io::Error> {
+        self.check_type_tag(Self::TRANSACTION_TYPE_TAG)?;
+        Transaction::deserialize(&self.payload)
+    }
+
+    /// Deserialize `payload` as a Receipt. Returns an error if `type_tag` is not [Envelope::RECEIPT_TYPE_TAG].
+    pub fn unwrap_receipt(&self) -> Result<Receipt, std::io::Error> {
+        self.check_type_tag(Self::RECEIPT_TYPE_TAG)?;
+        Receipt::deserialize(&self.payload)
+    }
+
+    /// Deserialize `payload` as a Block. Returns an error if `type_tag` is not [Envelope::BLOCK_TYPE_TAG].
+    pub fn unwrap_block(&self) -> Result<Block, std::io::Error> {
+        self.check_type_tag(Self::BLOCK_TYPE_TAG)?;
+        Block::deserialize(&self.payload)
+    }
+
+    fn check_type_tag(&self, expected: u8) -> Result<(), std::io::Error> {
+        if self.type_tag != expected {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Unexpected Envelope type tag"))
+        }
+        Ok(())
+    }
+}
+
+impl Serializable<Envelope> for Envelope {}
+impl Deserializable<Envelope> for Envelope {}
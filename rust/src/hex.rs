@@ -0,0 +1,85 @@
+/*
+ Copyright 2022 ParallelChain Lab
+
+ Licensed under the Apache License, Version 2.0 (the "License");
+ you may not use this file except in compliance with the License.
+ You may obtain a copy of the License at
+
+     http://www.apache.org/licenses/LICENSE-2.0
+
+ Unless required by applicable law or agreed to in writing, software
+ distributed under the License is distributed on an "AS IS" BASIS,
+ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ See the License for the specific language governing permissions and
+ limitations under the License.
+ */
+
+use std::ops::Deref;
+
+/// Hex are Strings restricted to containing lowercase hexadecimal digits, two per byte. Hex MUST NOT contain a `0x` prefix,
+/// but one is accepted when decoding.
+pub struct Hex(String);
+
+impl Hex {
+    /// encode takes in a slice of bytes and returns the bytes encoded as a Hex String.
+    pub fn encode<T: AsRef<[u8]>>(bytes: T) -> Hex {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let bytes = bytes.as_ref();
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for b in bytes {
+            hex.push(DIGITS[(b >> 4) as usize] as char);
+            hex.push(DIGITS[(b & 0x0f) as usize] as char);
+        }
+        Hex(hex)
+    }
+
+    /// decode takes in a string and tries to decode it into a Vector of bytes. Both uppercase and lowercase digits, and an
+    /// optional `0x` prefix, are accepted. It returns a HexDecodeError if `hex` is not valid Hex.
+    pub fn decode<T: ?Sized + AsRef<[u8]>>(hex: &T) -> Result<Vec<u8>, HexDecodeError> {
+        let hex = hex.as_ref();
+        let hex = hex.strip_prefix(b"0x").unwrap_or(hex);
+        if hex.len() % 2 != 0 {
+            return Err(HexDecodeError::OddLength)
+        }
+
+        let digit = |index: usize| -> Result<u8, HexDecodeError> {
+            match hex[index] {
+                c @ b'0'..=b'9' => Ok(c - b'0'),
+                c @ b'a'..=b'f' => Ok(c - b'a' + 10),
+                c @ b'A'..=b'F' => Ok(c - b'A' + 10),
+                c => Err(HexDecodeError::InvalidByte(index, c)),
+            }
+        };
+        (0..hex.len()).step_by(2)
+            .map(|i| Ok((digit(i)? << 4) | digit(i + 1)?))
+            .collect()
+    }
+}
+
+impl Deref for Hex {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+/// HexDecodeError is returned when a string is not valid Hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexDecodeError {
+    /// The string does not contain an even number of digits.
+    OddLength,
+    /// The byte at the contained offset (after any `0x` prefix) is not a hexadecimal digit.
+    InvalidByte(usize, u8),
+}
+
+impl std::fmt::Display for HexDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HexDecodeError::OddLength => write!(f, "odd number of hex digits"),
+            HexDecodeError::InvalidByte(index, byte) => write!(f, "invalid hex digit {:?} at offset {}", *byte as char, index),
+        }
+    }
+}
+
+impl std::error::Error for HexDecodeError {}
@@ -27,6 +27,10 @@ pub mod transaction;
 /// is the *only* binary-to-text encoding scheme used in ParallelChain F. 
 pub mod base64url;
 
+/// hex defines a type which implements the basic operations on hex encoded binary data, for interoperability with tools that
+/// expect hex. Base64URL remains the canonical encoding.
+pub mod hex;
+
 /// generic types implementation of traits Serializable and Deserializable
 pub mod blanket_impls;
 
@@ -43,6 +47,12 @@ pub mod crypto;
 /// a succinct way to describe what happened during the execution of the transaction. 
 pub mod receipt_status_codes;
 
+/// encodings defines [Envelope], a self-describing wrapper around serialized protocol types.
+pub mod encodings;
+
+/// serde_encodings defines helpers used by the `serde` feature to encode binary fields as Base64URL strings.
+#[cfg(feature = "serde")]
+pub mod serde_encodings;
 
 // Re-exports
 pub use sc_params::*;
@@ -50,38 +60,109 @@ pub use blanket_impls::*;
 pub use crypto::*;
 pub use transaction::*;
 pub use base64url::*;
+pub use hex::*;
 pub use block::*;
 pub use proofs::*;
 pub use receipt_status_codes::*;
+pub use encodings::*;
 
 
 /// Serializable encapsulates implementation of serialization on data structures that are defined in pchain-types.
 pub trait Serializable<T: borsh::BorshSerialize> {
+    /// Serialize `args` into a new Vec.
+    /// 
+    /// # Panics
+    /// Panics if serialization fails, e.g., if a collection in `args` is longer than u32::MAX. Use [Serializable::try_serialize]
+    /// for inputs of untrusted size.
     fn serialize(args: &T) -> Vec<u8> {
-        args.try_to_vec().unwrap()
+        Self::try_serialize(args).unwrap()
+    }
+
+    /// Serialize `args` into a new Vec, returning an error instead of panicking if serialization fails.
+    fn try_serialize(args: &T) -> Result<Vec<u8>, std::io::Error> {
+        args.try_to_vec()
+    }
+
+    /// Serialize `args` directly into `writer`, without allocating an intermediate buffer.
+    fn serialize_into<W: std::io::Write>(args: &T, writer: &mut W) -> std::io::Result<()> {
+        args.serialize(writer)
     }
 }
 
 /// Deserializable encapsulates implementation of deserialization on data structures that are defined in pchain-types.
 pub trait Deserializable<T : borsh::BorshDeserialize> {
+    /// Deserialize `args` into T. Returns an error if `args` is not exactly the serialization of a T, including if
+    /// there are bytes left over after deserializing it.
     fn deserialize(args: &[u8]) -> Result<T, std::io::Error> {
         T::try_from_slice(&args)
     }
+
+    /// Returns true if `args` deserializes into a T which serializes back into exactly `args`, i.e., if `args` is the one
+    /// canonical encoding of its value. Validators can use this to reject encodings that would cause hash mismatches.
+    fn is_canonical_encoding(args: &[u8]) -> bool where T: borsh::BorshSerialize {
+        match T::try_from_slice(args) {
+            Ok(t) => matches!(t.try_to_vec(), Ok(bytes) if bytes == args),
+            Err(_) => false,
+        }
+    }
+}
+
+/// DeserializationContext is carried by the `std::io::Error`s returned by hand-written deserialization code (e.g.,
+/// [Transaction::size_from_slice] and [Block::verify_receipt_codes]) to tell which part of the input it failed on. The
+/// error's `kind()` is unaffected. Retrieve the context with [DeserializationContext::of].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializationContext {
+    /// The field or region of the input that could not be deserialized, e.g., "transaction data"
+    pub context: &'static str,
+    /// Offset into the input at which the field or region starts
+    pub offset: usize,
+    /// Description of the failure
+    pub message: String,
+}
+
+impl DeserializationContext {
+    /// Get the DeserializationContext carried by `error`, if any.
+    pub fn of(error: &std::io::Error) -> Option<&DeserializationContext> {
+        error.get_ref()?.downcast_ref()
+    }
+
+    pub(crate) fn error(kind: std::io::ErrorKind, context: &'static str, offset: usize, message: &str) -> std::io::Error {
+        std::io::Error::new(kind, DeserializationContext { context, offset, message: message.to_string() })
+    }
+
+    /// Attach `context` and `offset` to `error`, keeping its kind and message.
+    pub(crate) fn wrap(error: std::io::Error, context: &'static str, offset: usize) -> std::io::Error {
+        std::io::Error::new(error.kind(), DeserializationContext { context, offset, message: error.to_string() })
+    }
 }
 
+impl std::fmt::Display for DeserializationContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at offset {}: {}", self.context, self.offset, self.message)
+    }
+}
+
+impl std::error::Error for DeserializationContext {}
 
 #[cfg(test)]
 mod test {
 
-    use std::convert::TryFrom;
+    use std::convert::{TryFrom, TryInto};
 
     use hotstuff_rs_types::messages;
+    use memory_db::{HashKey, MemoryDB};
+    use reference_trie::NoExtensionLayout;
+    use sha2::{Sha256, Digest};
+    use trie_db::{DBValue, TrieDBMutBuilder, TrieLayout, TrieMut};
 
     use crate::{
         Block, BlockHeader, Transaction, Receipt, Event,
-        Serializable, Deserializable, DeployTransactionData, MerkleProof, StateProofs, ReceiptStatusCode,
+        Serializable, Deserializable, DeployTransactionData, MerkleProof, StateProofs, ReceiptStatusCode, ReceiptStatusClass, Envelope, SERIALIZATION_VERSION,
+        StateProofError, ProofStats, Sha256Hash, PublicAddress, GasLimitExceeded, BlockReceiptsResult, TryFromHotStuffBlockError, NonceError, ReceiptsLengthMismatch, BlockError, BlockContentError, BlockBuilder, BlockHeaderMeta, BlockBuilderError, verify_transaction_inclusion, canonical_transaction_order, iter_transaction_bytes, BLOCK_GAS_LIMIT, BLOCK_SIZE_LIMIT,
     };
 
+    use crate::{crypto, Base64URL, Hex, HexDecodeError, Base64URLAddress, Base64URLHash, ParseBase64URLError, IncorrectLengthError, TxIngestError, CryptographicallyIncorrectTransactionError, TransactionStreamReader, TransactionKind, TransactionSummary, read_length_prefixed, write_length_prefixed, EventRef, EventFilter, DeserializationContext};
+
     use crate::{
         ParamsFromTransaction, ParamsFromBlockchain, CallData
     };
@@ -119,6 +200,17 @@ mod test {
         assert_eq!(tx_param.transaction_hash, deserialized.transaction_hash);
     }
 
+    #[test]
+    fn test_paramsfromtransaction_from_transaction() {
+        let tx = random_transaction(0, 128);
+        let tx_param = ParamsFromTransaction::from(&tx);
+        assert_eq!(tx_param.from_address, tx.from_address);
+        assert_eq!(tx_param.to_address, tx.to_address);
+        assert_eq!(tx_param.data, tx.data);
+        assert_eq!(tx_param.value, tx.value);
+        assert_eq!(tx_param.transaction_hash, tx.hash);
+    }
+
     #[test]
     fn test_paramsfromtransaction_error() {
         // test empty vector
@@ -169,6 +261,17 @@ mod test {
         assert_eq!(bc_param.random_bytes, deserialized.random_bytes);
     }
 
+    #[test]
+    fn test_paramsfromblockchain_from_header() {
+        let header = random_blockheader();
+        let random = random_bytes::<32>();
+        let bc_param = ParamsFromBlockchain::from_header(&header, random);
+        assert_eq!(bc_param.this_block_number, header.height);
+        assert_eq!(bc_param.prev_block_hash, header.justify.block_hash);
+        assert_eq!(bc_param.timestamp, header.timestamp);
+        assert_eq!(bc_param.random_bytes, random);
+    }
+
     #[test]
     fn test_paramsfromblockchain_error() {
         // test empty vector
@@ -202,6 +305,52 @@ mod test {
         assert_eq!(call_data.arguments, deserialized.arguments);
     }
 
+    #[test]
+    fn test_calldata_method_name_length() {
+        let call_data = CallData {
+            method_name: "m".repeat(CallData::MAX_METHOD_NAME_LENGTH),
+            arguments: random_bytes::<34>().to_vec()
+        };
+        let serialized = CallData::serialize(&call_data);
+        assert_eq!(CallData::deserialize(&serialized).unwrap(), call_data);
+
+        let call_data = CallData {
+            method_name: "m".repeat(CallData::MAX_METHOD_NAME_LENGTH + 1),
+            arguments: random_bytes::<34>().to_vec()
+        };
+        let serialized = CallData::serialize(&call_data);
+        assert_eq!(CallData::deserialize(&serialized).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+        // a buffer claiming a 4GB method name
+        let serialized = [&u32::MAX.to_le_bytes()[..], b"call"].concat();
+        assert_eq!(CallData::deserialize(&serialized).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_calldata_multiarg() {
+        let args = vec![1234u64.to_le_bytes().to_vec(), vec![], random_bytes::<34>().to_vec()];
+        let call_data = CallData::from_multiarg("transfer".to_string(), args.clone());
+        assert_eq!(call_data.method_name, "transfer");
+        assert_eq!(call_data.arguments_as_multiarg().unwrap(), args);
+
+        let expected_arguments = [
+            &3u32.to_le_bytes()[..],
+            &8u32.to_le_bytes(), &1234u64.to_le_bytes(),
+            &0u32.to_le_bytes(),
+            &34u32.to_le_bytes(), &args[2],
+        ].concat();
+        assert_eq!(call_data.arguments, expected_arguments);
+
+        // no arguments
+        let call_data = CallData::from_multiarg("get".to_string(), vec![]);
+        assert_eq!(call_data.arguments, 0u32.to_le_bytes().to_vec());
+        assert!(call_data.arguments_as_multiarg().unwrap().is_empty());
+
+        // arguments which are not a serialized Vec<Vec<u8>>
+        let call_data = CallData { method_name: "get".to_string(), arguments: vec![1, 0, 0, 0, 5] };
+        assert!(call_data.arguments_as_multiarg().is_err());
+    }
+
     #[test]
     fn test_calldata_error() {
         // test empty vector
@@ -245,6 +394,191 @@ mod test {
         assert_block(&block, &deserialized)
     }
 
+    #[test]
+    fn test_block_serialization_time() {
+        // Coarse regression guard. The bound is generous so that it holds in unoptimized builds on slow machines.
+        // See benches/serialization.rs for the benchmarks.
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(1000,1000,1024, 1024),
+            receipts: random_receipts(1000, 1000, 0,0,0, 32),
+        };
+
+        let t_before = std::time::Instant::now();
+        let serialized = Block::serialize(&block);
+        let deserialized = Block::deserialize(&serialized).unwrap();
+        assert!(t_before.elapsed() < std::time::Duration::from_secs(5));
+
+        assert_block(&block, &deserialized);
+    }
+
+    #[test]
+    fn test_serialize_into() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10,10,0, 128),
+            receipts: random_receipts(10, 10, 1,1,0, 128),
+        };
+
+        let mut buf = Vec::new();
+        Block::serialize_into(&block, &mut buf).unwrap();
+        assert_eq!(buf, Block::serialize(&block));
+
+        // serialize into a pre-sized buffer, after existing content
+        let mut buf = Vec::with_capacity(1024);
+        buf.push(1u8);
+        Transaction::serialize_into(&block.transactions[0], &mut buf).unwrap();
+        assert_eq!(buf[1..], Transaction::serialize(&block.transactions[0]));
+
+        // serialize into a fixed-size buffer that is too small
+        let mut buf = [0u8; 8];
+        assert!(Transaction::serialize_into(&block.transactions[0], &mut buf.as_mut_slice()).is_err());
+    }
+
+    #[test]
+    fn test_try_serialize() {
+        let tx = random_transaction(0, 128);
+        assert_eq!(Transaction::try_serialize(&tx).unwrap(), Transaction::serialize(&tx));
+
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10,10,0, 128),
+            receipts: random_receipts(10, 10, 1,1,0, 128),
+        };
+        assert_eq!(Block::try_serialize(&block).unwrap(), Block::serialize(&block));
+    }
+
+    #[test]
+    fn test_block_deserialize_verified() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3, 3, 0, 128),
+            receipts: random_receipts(3, 3, 0, 2, 0, 128),
+        };
+        block.header.txs_hash = crypto::merkle_root::<Transaction, Transaction>(&block.transactions);
+        block.header.receipts_hash = crypto::merkle_root::<Receipt, Receipt>(&block.receipts);
+        let serialized = Block::serialize(&block);
+        let deserialized = Block::deserialize_verified(&serialized).unwrap();
+        assert_eq!(Block::serialize(&deserialized), serialized);
+
+        // tampered txs_hash
+        let mut tampered = block.clone();
+        tampered.header.txs_hash[0] ^= 1;
+        let result = Block::deserialize_verified(&Block::serialize(&tampered));
+        assert!(matches!(result.err(), Some(BlockError::ContentMismatch(BlockContentError::WrongTxsHash))));
+
+        // tampered receipts_hash
+        let mut tampered = block.clone();
+        tampered.header.receipts_hash[31] ^= 1;
+        let result = Block::deserialize_verified(&Block::serialize(&tampered));
+        assert!(matches!(result.err(), Some(BlockError::ContentMismatch(BlockContentError::WrongReceiptsHash))));
+
+        // malformed bytes, keeping the kind of the underlying error
+        let truncated = &serialized[..serialized.len() - 1];
+        let kind = Block::deserialize(truncated).err().unwrap().kind();
+        assert!(matches!(Block::deserialize_verified(truncated), Err(BlockError::DeserializationFailed(e)) if e.kind() == kind));
+    }
+
+    #[test]
+    fn test_block_serialize_with_checksum() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3, 3, 0, 128),
+            receipts: random_receipts(3, 3, 0, 2, 0, 128),
+        };
+        let serialized = block.serialize_with_checksum();
+        assert_eq!(serialized[..serialized.len()-32], Block::serialize(&block)[..]);
+        let deserialized = Block::deserialize_with_checksum(&serialized).unwrap();
+        assert_eq!(Block::serialize(&deserialized), Block::serialize(&block));
+
+        // corrupted payload
+        let mut corrupted = serialized.clone();
+        corrupted[10] ^= 1;
+        assert!(matches!(Block::deserialize_with_checksum(&corrupted), Err(BlockError::ChecksumMismatch)));
+
+        // corrupted checksum
+        let mut corrupted = serialized.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(matches!(Block::deserialize_with_checksum(&corrupted), Err(BlockError::ChecksumMismatch)));
+
+        // missing checksum
+        assert!(matches!(Block::deserialize_with_checksum(&Block::serialize(&block)), Err(BlockError::ChecksumMismatch)));
+        assert!(matches!(Block::deserialize_with_checksum(&serialized[..31]), Err(BlockError::DeserializationFailed(_))));
+
+        // malformed payload with a matching checksum
+        let mut malformed = Block::serialize(&block);
+        malformed.pop();
+        let checksum = Sha256::digest(&malformed);
+        malformed.extend_from_slice(&checksum);
+        assert!(matches!(Block::deserialize_with_checksum(&malformed), Err(BlockError::DeserializationFailed(_))));
+    }
+
+    #[test]
+    fn test_block_try_serialize_mismatched_lengths() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3, 3, 0, 128),
+            receipts: random_receipts(2, 2, 1, 1, 0, 128),
+        };
+        assert!(matches!(Block::try_serialize(&block), Err(BlockError::ReceiptsLengthMismatch(ReceiptsLengthMismatch { transactions: 3, receipts: 2 }))));
+
+        // serialize does not check the structure
+        assert_eq!(<Block as Serializable<Block>>::try_serialize(&block).unwrap(), Block::serialize(&block));
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let tx = random_transaction(0, 128);
+        let serialized = Transaction::serialize(&tx);
+        let deserialized: Transaction = serialized.as_slice().try_into().unwrap();
+        assert_eq!(deserialized, tx);
+        assert!(Transaction::try_from(&serialized[1..]).is_err());
+
+        let receipt = random_receipt(2, 2, 0, 64);
+        assert_eq!(Receipt::try_from(Receipt::serialize(&receipt).as_slice()).unwrap(), receipt);
+        let event = receipt.events[0].clone();
+        assert_eq!(Event::try_from(Event::serialize(&event).as_slice()).unwrap(), event);
+
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(2, 2, 0, 64),
+            receipts: random_receipts(2, 2, 0, 2, 0, 64),
+        };
+        let serialized = Block::serialize(&block);
+        assert_eq!(Block::serialize(&Block::try_from(serialized.as_slice()).unwrap()), serialized);
+        let serialized = BlockHeader::serialize(&block.header);
+        assert_eq!(BlockHeader::serialize(&BlockHeader::try_from(serialized.as_slice()).unwrap()), serialized);
+        assert!(BlockHeader::try_from(&[][..]).is_err());
+    }
+
+    #[test]
+    fn test_block_serialized_size() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(100,100,0, 1024),
+            receipts: random_receipts(100, 100, 0,5,0, 1024),
+        };
+        assert_eq!(block.serialized_size(), Block::serialize(&block).len());
+        assert!(block.serialized_size() <= BLOCK_SIZE_LIMIT);
+
+        for tx in &block.transactions {
+            assert_eq!(tx.size_of(), Transaction::serialize(tx).len());
+        }
+        for recp in &block.receipts {
+            assert_eq!(recp.size_of(), Receipt::serialize(recp).len());
+            for evt in &recp.events {
+                assert_eq!(evt.size_of(), Event::serialize(evt).len());
+            }
+        }
+
+        let empty_block = Block{
+            header: random_blockheader(),
+            transactions: vec![],
+            receipts: vec![],
+        };
+        assert_eq!(empty_block.serialized_size(), Block::serialize(&empty_block).len());
+    }
+
     #[test]
     fn test_block_error() {
         // test empty vector
@@ -295,6 +629,670 @@ mod test {
         assert_eq!(Block::serialize(&block_1), Block::serialize(&block_2));
     }
 
+    #[test]
+    fn test_block_total_gas_consumed() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3,3,0, 128),
+            receipts: random_receipts(3, 3, 1,1,0, 128),
+        };
+        block.receipts[0].gas_consumed = 1;
+        block.receipts[1].gas_consumed = 20;
+        block.receipts[2].gas_consumed = 300;
+        assert_eq!(block.total_gas_consumed(), 321);
+
+        // saturates instead of overflowing
+        block.receipts[1].gas_consumed = u64::MAX;
+        assert_eq!(block.total_gas_consumed(), u64::MAX);
+
+        block.receipts.clear();
+        assert_eq!(block.total_gas_consumed(), 0);
+    }
+
+    #[test]
+    fn test_block_receipts_result() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10,10,0, 128),
+            receipts: random_receipts(10, 10, 0,5,0, 128),
+        };
+        let status_codes = [ReceiptStatusCode::Success, ReceiptStatusCode::RuntimeError, ReceiptStatusCode::WrongNonce];
+        for (i, recp) in block.receipts.iter_mut().enumerate() {
+            recp.status_code = status_codes[i % status_codes.len()].clone();
+            recp.gas_consumed = i as u64 * 1000;
+        }
+
+        let mut expected = BlockReceiptsResult::default();
+        for recp in &block.receipts {
+            expected.total_gas_consumed += recp.gas_consumed;
+            if recp.status_code == ReceiptStatusCode::Success {
+                expected.success_count += 1;
+            } else {
+                expected.failure_count += 1;
+            }
+            expected.total_events += recp.events.len();
+        }
+        assert_eq!(block.block_receipts_result(), Some(expected.clone()));
+        assert_eq!(expected.success_count, 4);
+        assert_eq!(expected.failure_count, 6);
+        assert_eq!(expected.total_gas_consumed, block.total_gas_consumed());
+
+        // total gas consumed overflows on a malformed block
+        block.receipts[0].gas_consumed = u64::MAX;
+        assert_eq!(block.block_receipts_result(), None);
+    }
+
+    #[test]
+    fn test_block_try_new() {
+        let block = Block::try_new(random_blockheader(), random_transactions(3, 3, 0, 16), random_receipts(3, 3, 0, 2, 0, 16)).unwrap();
+        assert!(block.validate_structure().is_ok());
+
+        let result = Block::try_new(random_blockheader(), random_transactions(3, 3, 0, 16), random_receipts(2, 2, 0, 2, 0, 16));
+        assert!(matches!(result.err(), Some(BlockError::ReceiptsLengthMismatch(ReceiptsLengthMismatch { transactions: 3, receipts: 2 }))));
+
+        // a block deserialized from a malformed message
+        let malformed = Block{ header: random_blockheader(), transactions: vec![], receipts: random_receipts(1, 1, 0, 0, 0, 0) };
+        let malformed = Block::deserialize(&Block::serialize(&malformed)).unwrap();
+        assert!(matches!(malformed.validate_structure(), Err(BlockError::ReceiptsLengthMismatch(ReceiptsLengthMismatch { transactions: 0, receipts: 1 }))));
+    }
+
+    #[test]
+    fn test_block_merkle_multiproof() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10, 10, 0, 64),
+            receipts: random_receipts(10, 10, 0, 0, 0, 0),
+        };
+        block.header.txs_hash = crypto::merkle_root::<Transaction, Transaction>(&block.transactions);
+
+        let proof = block.merkle_multiproof(&[7, 2, 5]).unwrap();
+        assert_eq!(proof.root_hash, block.header.txs_hash);
+        assert_eq!(proof.leaf_indices, vec![2, 5, 7]);
+        assert!(proof.verify());
+        for i in [2, 5, 7] {
+            assert!(verify_transaction_inclusion(&block.transactions[i], &proof, &block.header));
+        }
+        assert!(!verify_transaction_inclusion(&block.transactions[3], &proof, &block.header));
+
+        assert!(block.merkle_multiproof(&[2, 10]).is_none());
+    }
+
+    #[test]
+    fn test_block_iter_events() {
+        let mut receipts = random_receipts(4, 4, 0, 0, 0, 0);
+        receipts[0].events = random_events(2, 2, 1, 16);
+        receipts[2].events = random_events(3, 3, 1, 16);
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(4, 4, 0, 16),
+            receipts: receipts.clone(),
+        };
+
+        let events: Vec<(usize, &Event)> = block.iter_events().collect();
+        let expected: Vec<(usize, &Event)> = vec![
+            (0, &receipts[0].events[0]),
+            (0, &receipts[0].events[1]),
+            (2, &receipts[2].events[0]),
+            (2, &receipts[2].events[1]),
+            (2, &receipts[2].events[2]),
+        ];
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn test_block_filter_events() {
+        let event = |topic: &[u8]| Event { topic: topic.to_vec(), value: random_bytes::<8>().to_vec() };
+        let mut receipts = random_receipts(2, 2, 0, 0, 0, 0);
+        receipts[0].events = vec![event(b"transfer"), event(b"transfer_from"), event(b"mint")];
+        receipts[1].events = vec![event(b"trans"), event(b"transfer")];
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(2, 2, 0, 16),
+            receipts: receipts.clone(),
+        };
+        let topics = |filter: &EventFilter| -> Vec<(usize, Vec<u8>)> {
+            block.filter_events(filter).into_iter().map(|(i, e)| (i, e.topic.clone())).collect()
+        };
+
+        // match all
+        assert_eq!(block.filter_events(&EventFilter::default()).len(), 5);
+
+        // exact topic
+        let filter = EventFilter { exact_topic: Some(b"transfer".to_vec()), topic_prefix: None };
+        assert_eq!(topics(&filter), vec![(0, b"transfer".to_vec()), (1, b"transfer".to_vec())]);
+
+        // topic prefix
+        let filter = EventFilter { exact_topic: None, topic_prefix: Some(b"transfer".to_vec()) };
+        assert_eq!(topics(&filter), vec![(0, b"transfer".to_vec()), (0, b"transfer_from".to_vec()), (1, b"transfer".to_vec())]);
+
+        // exact topic takes precedence over topic prefix
+        let filter = EventFilter { exact_topic: Some(b"mint".to_vec()), topic_prefix: Some(b"trans".to_vec()) };
+        assert_eq!(topics(&filter), vec![(0, b"mint".to_vec())]);
+        assert!(filter.matches(&receipts[0].events[2]));
+        assert!(!filter.matches(&receipts[1].events[0]));
+    }
+
+    #[test]
+    fn test_block_sender_activity() {
+        let mut transactions = random_transactions(5, 5, 0, 16);
+        let sender = transactions[1].from_address;
+        transactions[3].from_address = sender;
+        transactions[4].from_address = sender;
+        let block = Block{
+            header: random_blockheader(),
+            transactions,
+            receipts: random_receipts(5, 5, 0, 2, 0, 16),
+        };
+
+        let activity = block.sender_activity(&sender);
+        assert_eq!(activity.len(), 3);
+        for ((tx, receipt), i) in activity.into_iter().zip([1, 3, 4]) {
+            assert_eq!(*tx, block.transactions[i]);
+            assert_eq!(*receipt, block.receipts[i]);
+        }
+
+        assert!(block.sender_activity(&random_bytes::<32>()).is_empty());
+    }
+
+    #[test]
+    fn test_block_touched_addresses() {
+        let mut transactions = random_transactions(4, 4, 0, 16);
+        transactions[1].from_address = transactions[0].from_address;
+        transactions[2].to_address = transactions[0].from_address;
+        transactions[3].to_address = transactions[3].from_address;
+        let block = Block{
+            header: random_blockheader(),
+            transactions: transactions.clone(),
+            receipts: vec![],
+        };
+
+        let touched = block.touched_addresses();
+        for tx in &transactions {
+            assert!(touched.contains(&tx.from_address));
+            assert!(touched.contains(&tx.to_address));
+        }
+        assert_eq!(touched.len(), 5);
+
+        let empty_block = Block{ header: random_blockheader(), transactions: vec![], receipts: vec![] };
+        assert!(empty_block.touched_addresses().is_empty());
+    }
+
+    #[test]
+    fn test_block_ordering() {
+        let block_at = |height: u64, hash: [u8; 32]| {
+            let mut header = random_blockheader();
+            header.height = height;
+            header.hash = hash;
+            Block{ header, transactions: vec![], receipts: vec![] }
+        };
+
+        let mut blocks = [
+            block_at(3, [0u8; 32]),
+            block_at(1, [9u8; 32]),
+            block_at(2, [5u8; 32]),
+            block_at(0, [7u8; 32]),
+            block_at(2, [4u8; 32]),
+        ];
+        blocks.sort();
+        let order: Vec<(u64, u8)> = blocks.iter().map(|b| (b.header.height, b.header.hash[0])).collect();
+        assert_eq!(order, vec![(0, 7), (1, 9), (2, 4), (2, 5), (3, 0)]);
+
+        // blocks with the same height and hash are equal regardless of their contents
+        let mut other = block_at(1, [9u8; 32]);
+        other.transactions.push(random_transaction(0, 10));
+        assert!(blocks[1] == other);
+        assert!(blocks[2] < blocks[3]);
+        assert!(blocks[4] > blocks[3]);
+    }
+
+    #[test]
+    fn test_iter_transaction_bytes() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(5, 5, 0, 256),
+            receipts: random_receipts(5, 5, 0, 3, 0, 128),
+        };
+        let serialized = Block::serialize(&block);
+        let tx_bytes: Vec<&[u8]> = iter_transaction_bytes(&serialized).unwrap().collect();
+        assert_eq!(tx_bytes.len(), 5);
+        for (bytes, tx) in tx_bytes.into_iter().zip(block.transactions.iter()) {
+            assert_eq!(Transaction::deserialize(bytes).unwrap(), *tx);
+        }
+
+        let empty_block = Block{ header: random_blockheader(), transactions: vec![], receipts: vec![] };
+        assert_eq!(iter_transaction_bytes(&Block::serialize(&empty_block)).unwrap().count(), 0);
+
+        // truncated in the transactions
+        let header_size = BlockHeader::serialize(&block.header).len();
+        assert!(iter_transaction_bytes(&serialized[..header_size + 4 + block.transactions[0].size_of()]).is_err());
+    }
+
+    #[test]
+    fn test_block_verify_receipt_codes() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(5,5,0, 128),
+            receipts: random_receipts(5, 5, 0,3,0, 128),
+        };
+        let serialized = Block::serialize(&block);
+        assert!(Block::verify_receipt_codes(&serialized).is_ok());
+
+        // corrupt the status code of the last receipt
+        let last_receipt_offset = serialized.len() - Receipt::serialize(&block.receipts[4]).len();
+        let mut corrupted = serialized.clone();
+        corrupted[last_receipt_offset] = 99;
+        assert!(Block::verify_receipt_codes(&corrupted).is_err());
+
+        // truncated block
+        assert!(Block::verify_receipt_codes(&serialized[..serialized.len()-1]).is_err());
+    }
+
+    #[test]
+    fn test_transaction_hash_collections() {
+        let txs = random_transactions(3, 3, 0, 64);
+        let set: std::collections::HashSet<Transaction> = txs.iter().chain(txs.iter()).cloned().collect();
+        assert_eq!(set.len(), 3);
+        assert!(txs.iter().all(|tx| set.contains(tx)));
+
+        let keys: std::collections::HashSet<Sha256Hash> = txs.iter().chain(txs.iter()).map(Transaction::dedup_key).collect();
+        assert_eq!(keys.len(), 3);
+        assert!(txs.iter().all(|tx| keys.contains(&tx.hash)));
+
+        let receipts = random_receipts(2, 2, 1, 3, 0, 16);
+        let set: std::collections::HashSet<Receipt> = receipts.iter().chain(receipts.iter()).cloned().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_transaction_base_gas_cost() {
+        let mut tx = random_transaction(0, 0);
+        tx.data = vec![];
+        assert_eq!(tx.base_gas_cost(10), 204 * 10);
+
+        tx.data = vec![1u8; 1000];
+        assert_eq!(tx.base_gas_cost(10), Transaction::serialize(&tx).len() as u64 * 10);
+        assert_eq!(tx.base_gas_cost(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_transaction_actual_fee() {
+        let mut tx = random_transaction(0, 16);
+        tx.gas_price = 8;
+        tx.tip = 100;
+        let mut receipt = random_receipt(0, 0, 0, 0);
+        receipt.gas_consumed = 21_000;
+        assert_eq!(tx.actual_fee(&receipt), Some(21_000 * 8 + 100));
+
+        receipt.gas_consumed = 0;
+        assert_eq!(tx.actual_fee(&receipt), Some(100));
+
+        // overflow
+        receipt.gas_consumed = u64::MAX;
+        assert_eq!(tx.actual_fee(&receipt), None);
+        receipt.gas_consumed = u64::MAX / 8;
+        tx.tip = u64::MAX;
+        assert_eq!(tx.actual_fee(&receipt), None);
+    }
+
+    #[test]
+    fn test_deserialization_context() {
+        // truncated transaction
+        let mut tx = random_transaction(0, 0);
+        tx.data = vec![1u8; 100];
+        let serialized = Transaction::serialize(&tx);
+        let e = Transaction::size_from_slice(&serialized[..150]).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+        let context = DeserializationContext::of(&e).unwrap();
+        assert_eq!((context.context, context.offset), ("transaction data", 100));
+
+        let e = Transaction::size_from_slice(&serialized[..250]).unwrap_err();
+        let context = DeserializationContext::of(&e).unwrap();
+        assert_eq!((context.context, context.offset), ("transaction nonce, hash and signature", 200));
+
+        // corrupted receipt in a block
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(2, 2, 0, 128),
+            receipts: random_receipts(2, 2, 0, 2, 0, 128),
+        };
+        let mut serialized = Block::serialize(&block);
+        let receipt_offset = serialized.len() - Receipt::serialize(&block.receipts[1]).len();
+        serialized[receipt_offset] = 99;
+        let e = Block::verify_receipt_codes(&serialized).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        let context = DeserializationContext::of(&e).unwrap();
+        assert_eq!((context.context, context.offset), ("receipt status code", receipt_offset));
+        assert_eq!(e.to_string(), format!("receipt status code at offset {}: Invalid receipt status code 99", receipt_offset));
+
+        // truncated transaction in a block
+        let serialized = Block::serialize(&block);
+        let header_size = BlockHeader::serialize(&block.header).len();
+        let e = Block::verify_receipt_codes(&serialized[..header_size + 4 + 50]).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+        let context = DeserializationContext::of(&e).unwrap();
+        assert_eq!((context.context, context.offset), ("transaction", header_size + 4));
+
+        // errors from borsh carry no context
+        assert!(DeserializationContext::of(&Transaction::deserialize(&[]).unwrap_err()).is_none());
+    }
+
+    #[test]
+    fn test_transaction_size_from_slice() {
+        let tx = random_transaction(0, 1024);
+        let mut serialized = Transaction::serialize(&tx);
+        assert_eq!(Transaction::size_from_slice(&serialized).unwrap(), tx.size_of());
+
+        serialized.extend_from_slice(&[1u8; 10]);
+        assert_eq!(Transaction::size_from_slice(&serialized).unwrap(), tx.size_of());
+
+        assert!(Transaction::size_from_slice(&serialized[..tx.size_of()-1]).is_err());
+        assert!(Transaction::size_from_slice(&serialized[..99]).is_err());
+    }
+
+    #[test]
+    fn test_block_verify_content_hashes() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(5,5,0, 128),
+            receipts: random_receipts(5, 5, 1,1,0, 128),
+        };
+        block.header.txs_hash = crypto::merkle_root::<Transaction, Transaction>(&block.transactions);
+        block.header.receipts_hash = crypto::merkle_root::<Receipt, Receipt>(&block.receipts);
+        assert_eq!(block.verify_content_hashes(), Ok(()));
+
+        let mut wrong_block = block.clone();
+        wrong_block.transactions.swap(0, 1);
+        assert_eq!(wrong_block.verify_content_hashes(), Err(BlockContentError::WrongTxsHash));
+
+        let mut wrong_block = block;
+        wrong_block.receipts.pop();
+        assert_eq!(wrong_block.verify_content_hashes(), Err(BlockContentError::WrongReceiptsHash));
+    }
+
+    #[test]
+    fn test_block_verify_gas_limit() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3,3,0, 128),
+            receipts: random_receipts(3, 3, 1,1,0, 128),
+        };
+        let limit = BLOCK_GAS_LIMIT as u64;
+
+        // exactly at the limit
+        block.receipts[0].gas_consumed = limit - 2;
+        block.receipts[1].gas_consumed = 1;
+        block.receipts[2].gas_consumed = 1;
+        assert_eq!(block.verify_gas_limit(), Ok(()));
+
+        // one over the limit
+        block.receipts[2].gas_consumed = 2;
+        assert_eq!(block.verify_gas_limit(), Err(GasLimitExceeded { total_gas_consumed: limit + 1, limit }));
+
+        // overflowing total
+        block.receipts[1].gas_consumed = u64::MAX;
+        assert_eq!(block.verify_gas_limit(), Err(GasLimitExceeded { total_gas_consumed: u64::MAX, limit }));
+    }
+
+    #[test]
+    fn test_block_iter_with_receipts() {
+        let transactions = random_transactions(5,5,0, 128);
+        let receipts = random_receipts(5, 5, 1,1,0, 128);
+        let block = Block::try_new(random_blockheader(), transactions.clone(), receipts.clone()).unwrap();
+
+        let pairs: Vec<(&Transaction, &Receipt)> = block.iter_with_receipts().collect();
+        assert_eq!(pairs.len(), 5);
+        for (i, (tx, recp)) in pairs.into_iter().enumerate() {
+            assert_eq!(tx, &transactions[i]);
+            assert_eq!(recp, &receipts[i]);
+        }
+
+        // test different numbers of transactions and receipts
+        let block = Block { header: random_blockheader(), transactions, receipts: receipts[..4].to_vec() };
+        assert_eq!(block.iter_with_receipts().count(), 4);
+    }
+
+    #[test]
+    fn test_block_transactions_above_gas_price() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(5,5,0, 128),
+            receipts: random_receipts(5, 5, 1,1,0, 128),
+        };
+        for (i, tx) in block.transactions.iter_mut().enumerate() {
+            tx.gas_price = [3, 1, 4, 1, 5][i];
+        }
+
+        let gas_prices: Vec<u64> = block.transactions_above_gas_price(3).map(|tx| tx.gas_price).collect();
+        assert_eq!(gas_prices, vec![3, 4, 5]);
+        assert_eq!(block.transactions_above_gas_price(0).count(), 5);
+        assert_eq!(block.transactions_above_gas_price(6).count(), 0);
+    }
+
+    #[test]
+    fn test_block_all_senders_valid() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: (0..4).map(|_| signed_transaction(&keypair)).collect(),
+            receipts: random_receipts(4, 4, 1,1,0, 128),
+        };
+        assert_eq!(block.all_senders_valid(), Ok(()));
+
+        // [2u8; 32] is not a point on the curve
+        block.transactions[2].from_address = [2u8; 32];
+        assert_eq!(block.all_senders_valid(), Err(2));
+    }
+
+    #[test]
+    fn test_block_verify_nonce_sequences() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(5,5,0, 128),
+            receipts: random_receipts(5, 5, 1,1,0, 128),
+        };
+        let senders = [[1u8; 32], [2u8; 32], [1u8; 32], [2u8; 32], [1u8; 32]];
+        let nonces = [0, 7, 1, 8, 5];
+        for (i, tx) in block.transactions.iter_mut().enumerate() {
+            tx.from_address = senders[i];
+            tx.n_txs_on_chain_from_address = nonces[i];
+        }
+        assert_eq!(block.verify_nonce_sequences(), Ok(()));
+
+        // repeated nonce
+        block.transactions[3].n_txs_on_chain_from_address = 7;
+        assert_eq!(block.verify_nonce_sequences(), Err(NonceError { from_address: [2u8; 32], index: 3 }));
+
+        // decreasing nonce
+        block.transactions[3].n_txs_on_chain_from_address = 8;
+        block.transactions[4].n_txs_on_chain_from_address = 0;
+        assert_eq!(block.verify_nonce_sequences(), Err(NonceError { from_address: [1u8; 32], index: 4 }));
+    }
+
+    #[test]
+    fn test_block_balance_delta_for() {
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(4,4,0, 128),
+            receipts: random_receipts(4, 4, 1,1,0, 128),
+        };
+        // (from, to, value, gas_price, tip, gas_consumed, status_code)
+        let txs = [
+            (alice, bob, 100, 2, 1, 10, ReceiptStatusCode::Success),
+            (bob, alice, 30, 1, 0, 5, ReceiptStatusCode::Success),
+            (alice, bob, 1000, 1, 0, 7, ReceiptStatusCode::RuntimeError),
+            (alice, alice, 50, 3, 2, 4, ReceiptStatusCode::Success),
+        ];
+        for (i, (from, to, value, gas_price, tip, gas_consumed, status_code)) in txs.iter().enumerate() {
+            block.transactions[i].from_address = *from;
+            block.transactions[i].to_address = *to;
+            block.transactions[i].value = *value;
+            block.transactions[i].gas_price = *gas_price;
+            block.transactions[i].tip = *tip;
+            block.receipts[i].gas_consumed = *gas_consumed;
+            block.receipts[i].status_code = status_code.clone();
+        }
+
+        assert_eq!(block.balance_delta_for(&alice), -(100 + 20 + 1) + 30 - (12 + 2));
+        assert_eq!(block.balance_delta_for(&bob), 100 - (30 + 5));
+        assert_eq!(block.balance_delta_for(&[3u8; 32]), 0);
+
+        // saturates instead of overflowing
+        block.transactions[0].value = u64::MAX;
+        block.transactions[0].gas_price = u64::MAX;
+        block.receipts[0].gas_consumed = u64::MAX;
+        assert!(block.balance_delta_for(&alice) < -(u64::MAX as i128));
+    }
+
+    #[test]
+    fn test_block_data_slots() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(3,3,0, 128),
+            receipts: random_receipts(3, 3, 1,1,0, 128),
+        };
+        let data_slots = block.data_slots();
+        assert_eq!(data_slots.len(), Block::NUM_SLOTS + 6);
+        assert_eq!(data_slots[Block::VERSION_SLOT], block.header.version_number.to_le_bytes().to_vec());
+        assert_eq!(data_slots[Block::TIMESTAMP_SLOT], block.header.timestamp.to_le_bytes().to_vec());
+        assert_eq!(data_slots[Block::TXS_HASH_SLOT], block.header.txs_hash.to_vec());
+        assert_eq!(data_slots[Block::STATE_HASH_SLOT], block.header.state_hash.to_vec());
+        assert_eq!(data_slots[Block::RECEIPTS_HASH_SLOT], block.header.receipts_hash.to_vec());
+        assert_eq!(data_slots[Block::NUM_SLOTS], Transaction::serialize(&block.transactions[0]));
+        assert_eq!(data_slots[Block::NUM_SLOTS + 3], Receipt::serialize(&block.receipts[0]));
+    }
+
+    #[test]
+    fn test_block_try_from_hotstuff_block() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let block = Block{
+            header: random_blockheader(),
+            transactions: vec![signed_transaction(&keypair), signed_transaction(&keypair)],
+            receipts: random_receipts(2, 2, 1,1,0, 128),
+        };
+        let hotstuff_block = messages::Block {
+            app_id: block.header.app_id,
+            height: block.header.height,
+            justify: block.header.justify.clone(),
+            data_hash: block.header.data_hash,
+            data: block.data_slots(),
+            hash: block.header.hash,
+        };
+        let converted = Block::try_from(hotstuff_block.clone()).unwrap();
+        assert_block(&block, &converted);
+
+        // test transaction with wrong hash
+        let mut data = block.data_slots();
+        let mut tx = block.transactions[1].clone();
+        tx.hash = [0u8; 32];
+        data[Block::NUM_SLOTS + 1] = Transaction::serialize(&tx);
+        assert!(matches!(
+            Block::try_from(messages::Block { data, ..hotstuff_block }),
+            Err(TryFromHotStuffBlockError::WronglyAuthenticatedTransaction(CryptographicallyIncorrectTransactionError::WrongHash))
+        ));
+    }
+
+    #[test]
+    fn test_block_builder() {
+        let transactions = random_transactions(10,10,0, 128);
+        let receipts = random_receipts(10, 10, 1,1,0, 128);
+
+        let header = random_blockheader();
+        let mut builder = BlockBuilder::new(blockheader_meta(&header));
+        for (tx, recp) in transactions.iter().zip(receipts.iter()) {
+            let mut recp = recp.clone();
+            recp.gas_consumed = 1000;
+            builder.push_transaction(tx.clone(), recp).unwrap();
+        }
+
+        let tentative_size = builder.tentative_size();
+        // the data hash is computed over the block with its content hashes
+        let block = builder.finalize(|block| {
+            assert_eq!(block.verify_content_hashes(), Ok(()));
+            header.data_hash
+        }).unwrap();
+
+        assert_eq!(block.transactions, transactions);
+        assert_eq!(block.header.app_id, header.app_id);
+        assert_eq!(block.header.state_hash, header.state_hash);
+        assert_eq!(block.serialized_size(), tentative_size);
+        assert_eq!(block.verify_content_hashes(), Ok(()));
+        assert_eq!(block.header.data_hash, header.data_hash);
+        assert_eq!(block.header.hash, block.header.compute_hash());
+        assert_eq!(block.verify_gas_limit(), Ok(()));
+    }
+
+    #[test]
+    fn test_block_builder_error() {
+        let header_meta = blockheader_meta(&random_blockheader());
+        let mut builder = BlockBuilder::new(header_meta.clone());
+        let mut recp = random_receipt(0, 0, 0, 0);
+
+        // gas limit
+        recp.gas_consumed = BLOCK_GAS_LIMIT as u64;
+        builder.push_transaction(random_transaction(0, 0), recp.clone()).unwrap();
+        recp.gas_consumed = 1;
+        assert_eq!(builder.push_transaction(random_transaction(0, 0), recp.clone()), Err(BlockBuilderError::GasLimitExceeded));
+
+        // size limit
+        let mut builder = BlockBuilder::new(header_meta.clone());
+        recp.gas_consumed = 0;
+        builder.push_transaction(random_transaction(BLOCK_SIZE_LIMIT / 2, BLOCK_SIZE_LIMIT / 2), recp.clone()).unwrap();
+        let tentative_size = builder.tentative_size();
+        assert_eq!(
+            builder.push_transaction(random_transaction(BLOCK_SIZE_LIMIT / 2, BLOCK_SIZE_LIMIT / 2), recp.clone()),
+            Err(BlockBuilderError::SizeLimitExceeded)
+        );
+        assert_eq!(builder.tentative_size(), tentative_size);
+
+        // size limit, counting a header with a large justify
+        let mut large_header_meta = header_meta;
+        large_header_meta.justify.sigs.signatures = vec![Some([1u8; 64]); 1000];
+        let mut builder = BlockBuilder::new(large_header_meta);
+        let header_size = builder.tentative_size();
+        let max_data_size = BLOCK_SIZE_LIMIT - header_size - 204 - recp.size_of();
+        assert_eq!(
+            builder.push_transaction(random_transaction(max_data_size + 1, max_data_size + 1), recp.clone()),
+            Err(BlockBuilderError::SizeLimitExceeded)
+        );
+        builder.push_transaction(random_transaction(max_data_size, max_data_size), recp.clone()).unwrap();
+        let block = builder.finalize(|_| [0u8; 32]).unwrap();
+        assert_eq!(block.serialized_size(), BLOCK_SIZE_LIMIT);
+
+        // the header alone exceeds the size limit
+        let mut huge_header_meta = blockheader_meta(&random_blockheader());
+        huge_header_meta.justify.sigs.signatures = vec![Some([1u8; 64]); BLOCK_SIZE_LIMIT / 64];
+        assert_eq!(BlockBuilder::new(huge_header_meta).finalize(|_| [0u8; 32]).err(), Some(BlockBuilderError::SizeLimitExceeded));
+    }
+
+    #[test]
+    fn test_canonical_transaction_order() {
+        let mut transactions = random_transactions(20,20,0, 16);
+        for (i, tx) in transactions.iter_mut().enumerate() {
+            tx.gas_price = (i % 3) as u64;
+            tx.from_address = [(i % 2) as u8; 32];
+        }
+
+        let mut shuffled_1 = transactions.clone();
+        shuffled_1.reverse();
+        let mut shuffled_2 = transactions.clone();
+        shuffled_2.rotate_left(7);
+
+        canonical_transaction_order(&mut transactions);
+        canonical_transaction_order(&mut shuffled_1);
+        canonical_transaction_order(&mut shuffled_2);
+        assert_eq!(transactions, shuffled_1);
+        assert_eq!(transactions, shuffled_2);
+
+        for pair in transactions.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            assert!(a.gas_price > b.gas_price
+                || (a.gas_price == b.gas_price && a.from_address < b.from_address)
+                || (a.gas_price == b.gas_price && a.from_address == b.from_address && a.n_txs_on_chain_from_address <= b.n_txs_on_chain_from_address));
+        }
+    }
+
     #[test]
     fn test_vec_blocks(){
         let mut blocks = vec![];
@@ -357,6 +1355,35 @@ mod test {
         assert_eq!(b.receipts_hash, deserialized.receipts_hash);
     }
 
+    #[test]
+    fn test_blockheader_hash() {
+        let header = random_blockheader();
+        let mut preimage = header.app_id.to_le_bytes().to_vec();
+        preimage.extend_from_slice(&header.height.to_le_bytes());
+        preimage.extend_from_slice(&borsh::BorshSerialize::try_to_vec(&header.justify).unwrap());
+        preimage.extend_from_slice(&header.data_hash);
+        assert_eq!(header.hash_preimage(), preimage);
+
+        // the hash matches the hash of the HotStuff block that the block is converted from
+        let hotstuff_block = messages::Block::new(header.app_id, header.height, header.justify.clone(), header.data_hash, vec![
+            header.version_number.to_le_bytes().to_vec(),
+            header.timestamp.to_le_bytes().to_vec(),
+            header.txs_hash.to_vec(),
+            header.state_hash.to_vec(),
+            header.receipts_hash.to_vec(),
+        ]);
+        let hotstuff_hash = hotstuff_block.hash;
+        let block = Block::try_from(hotstuff_block).unwrap();
+        assert_eq!(block.header.compute_hash(), hotstuff_hash);
+
+        // the hash does not depend on the existing `hash`, but does on `data_hash`
+        let mut header = block.header;
+        header.hash = [0u8; 32];
+        assert_eq!(header.compute_hash(), hotstuff_hash);
+        header.data_hash[0] ^= 1;
+        assert_ne!(header.compute_hash(), hotstuff_hash);
+    }
+
     #[test]
     fn test_blockheader_error() {
         // test by removing one byte
@@ -448,6 +1475,330 @@ mod test {
 
     }
 
+    #[test]
+    fn test_deserialize_trailing_bytes() {
+        let tx = random_transaction(0, 128);
+        let mut serialized = Transaction::serialize(&tx);
+        serialized.push(0);
+        assert!(Transaction::deserialize(&serialized).is_err());
+
+        let params = ParamsFromTransaction {
+            from_address: random_bytes::<32>(),
+            to_address: random_bytes::<32>(),
+            data: random_bytes::<16>().to_vec(),
+            value: 1,
+            transaction_hash: random_bytes::<32>(),
+        };
+        let mut serialized = ParamsFromTransaction::serialize(&params);
+        serialized.push(0);
+        assert!(ParamsFromTransaction::deserialize(&serialized).is_err());
+
+        let call_data = CallData { method_name: "method".to_string(), arguments: vec![1, 2, 3] };
+        let mut serialized = CallData::serialize(&call_data);
+        serialized.push(0);
+        assert!(CallData::deserialize(&serialized).is_err());
+    }
+
+    #[test]
+    fn test_is_canonical_encoding() {
+        let tx = random_transaction(0, 128);
+        let serialized = Transaction::serialize(&tx);
+        assert!(Transaction::is_canonical_encoding(&serialized));
+
+        // data length prefix which disagrees with the data
+        let mut non_canonical = serialized.clone();
+        non_canonical[96] = non_canonical[96].wrapping_add(1);
+        assert!(!Transaction::is_canonical_encoding(&non_canonical));
+
+        // trailing bytes
+        let mut non_canonical = serialized;
+        non_canonical.push(0);
+        assert!(!Transaction::is_canonical_encoding(&non_canonical));
+
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10,10,0, 128),
+            receipts: random_receipts(10, 10, 1,1,0, 128),
+        };
+        assert!(Block::is_canonical_encoding(&Block::serialize(&block)));
+        assert!(!Block::is_canonical_encoding(&[]));
+    }
+
+    #[test]
+    fn test_transaction_deserialize_and_verify() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let tx = signed_transaction(&keypair);
+        let serialized = Transaction::serialize(&tx);
+
+        let deserialized = Transaction::deserialize_and_verify(&serialized);
+        assert!(deserialized.is_ok());
+        assert_transaction(&tx, &deserialized.ok().unwrap());
+    }
+
+    #[test]
+    fn test_transaction_deserialize_and_verify_error() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+
+        // test by removing one byte
+        let serialized = Transaction::serialize(&signed_transaction(&keypair));
+        let serialized = serialized[..serialized.len()-1].to_vec();
+        assert!(matches!(Transaction::deserialize_and_verify(&serialized), Err(TxIngestError::WronglySerialized(_))));
+
+        // test by changing a signed field
+        let mut tx = signed_transaction(&keypair);
+        tx.value += 1;
+        let serialized = Transaction::serialize(&tx);
+        assert!(matches!(
+            Transaction::deserialize_and_verify(&serialized), 
+            Err(TxIngestError::CryptographicallyIncorrect(CryptographicallyIncorrectTransactionError::WrongSignature))
+        ));
+    }
+
+    #[test]
+    fn test_cryptographically_incorrect_transaction_error() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let tx = signed_transaction(&keypair);
+
+        let wrong_signature = Transaction { value: tx.value.wrapping_add(1), ..tx.clone() };
+        assert_eq!(wrong_signature.verify_cryptographic_correctness(), Err(CryptographicallyIncorrectTransactionError::WrongSignature));
+
+        let wrong_hash = Transaction { hash: [0u8; 32], ..tx.clone() };
+        assert_eq!(wrong_hash.verify_cryptographic_correctness(), Err(CryptographicallyIncorrectTransactionError::WrongHash));
+
+        // errors can be boxed and displayed with distinct messages
+        let errors: Vec<Box<dyn std::error::Error>> = vec![
+            Box::new(CryptographicallyIncorrectTransactionError::InvalidFromAddress),
+            Box::new(CryptographicallyIncorrectTransactionError::InvalidSignature),
+            Box::new(CryptographicallyIncorrectTransactionError::WrongSignature),
+            Box::new(CryptographicallyIncorrectTransactionError::WrongHash),
+        ];
+        let messages: std::collections::HashSet<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), errors.len());
+    }
+
+    #[test]
+    fn test_transaction_sign() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let mut tx = Transaction {
+            from_address: [7u8; 32],
+            to_address: [1u8; 32],
+            value: 1,
+            tip: 0,
+            gas_limit: 100,
+            gas_price: 1,
+            data: vec![],
+            n_txs_on_chain_from_address: 0,
+            hash: [0u8; 32],
+            signature: [0u8; 64],
+        };
+        tx.sign(&keypair);
+
+        // from_address is overridden by the public key of the keypair
+        assert_eq!(tx.from_address, keypair.public.to_bytes());
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+    }
+
+    #[test]
+    fn test_transaction_verify_hash() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let tx = signed_transaction(&keypair);
+        assert!(tx.verify_hash());
+
+        // the signature itself is not verified
+        let tx_with_wrong_signature = Transaction { value: tx.value.wrapping_add(1), ..tx.clone() };
+        assert!(tx_with_wrong_signature.verify_hash());
+
+        let mut tampered_tx = tx;
+        tampered_tx.hash[0] ^= 1;
+        assert!(!tampered_tx.verify_hash());
+    }
+
+    // Besides the derived Borsh serialization, Transactions are encoded and decoded field by field in Transaction::signable_bytes,
+    // Transaction::size_of, Transaction::size_from_slice and TransactionStreamReader. This pins the byte layout that all of them
+    // assume, so that a change to the fields of Transaction fails here rather than causing them to silently drift apart.
+    #[test]
+    fn test_transaction_serialization_layout() {
+        let tx = Transaction {
+            from_address: [1u8; 32],
+            to_address: [2u8; 32],
+            value: 0x0303030303030303,
+            tip: 0x0404040404040404,
+            gas_limit: 0x0505050505050505,
+            gas_price: 0x0606060606060606,
+            data: vec![7u8; 3],
+            n_txs_on_chain_from_address: 0x0808080808080808,
+            hash: [9u8; 32],
+            signature: [10u8; 64],
+        };
+        let expected = [
+            &[1u8; 32][..],
+            &[2u8; 32],
+            &[3u8; 8],
+            &[4u8; 8],
+            &[5u8; 8],
+            &[6u8; 8],
+            &3u32.to_le_bytes(), &[7u8; 3],
+            &[8u8; 8],
+            &[9u8; 32],
+            &[10u8; 64],
+        ].concat();
+
+        let serialized = Transaction::serialize(&tx);
+        assert_eq!(serialized, expected);
+        assert_eq!(tx.size_of(), expected.len());
+        assert_eq!(Transaction::size_from_slice(&expected).unwrap(), expected.len());
+        assert_eq!(tx.signable_bytes()[..expected.len() - 96], expected[..expected.len() - 96]);
+
+        let stream = [1u32.to_le_bytes().to_vec(), expected].concat();
+        let streamed: Vec<Transaction> = TransactionStreamReader::new(&stream[..]).map(Result::unwrap).collect();
+        assert_eq!(streamed, vec![tx]);
+    }
+
+    #[test]
+    fn test_transaction_transfer() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let to_address = random_bytes::<32>();
+        let mut tx = Transaction::transfer(to_address, 100, 50_000, 8, 3);
+        assert_eq!(tx.kind(), TransactionKind::Transfer);
+        assert_eq!(tx.from_address, [0u8; 32]);
+        assert!(tx.verify_cryptographic_correctness().is_err());
+
+        tx.sign(&keypair);
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+        assert_eq!(tx.from_address, keypair.public.to_bytes());
+        assert_eq!(tx.to_address, to_address);
+        assert_eq!(tx.value, 100);
+        assert_eq!(tx.gas_limit, 50_000);
+        assert_eq!(tx.gas_price, 8);
+        assert_eq!(tx.n_txs_on_chain_from_address, 3);
+        assert!(tx.data.is_empty());
+    }
+
+    #[test]
+    fn test_transaction_unsigned() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let tx = signed_transaction(&keypair);
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+
+        let mut unsigned = tx.clone().unsigned();
+        assert_eq!(unsigned.hash, [0u8; 32]);
+        assert_eq!(unsigned.signature, [0u8; 64]);
+        assert_eq!(unsigned.signable_bytes(), tx.signable_bytes());
+        assert!(unsigned.verify_cryptographic_correctness().is_err());
+
+        // fee bump
+        unsigned.gas_price += 1;
+        unsigned.sign(&keypair);
+        assert!(unsigned.verify_cryptographic_correctness().is_ok());
+        assert_ne!(unsigned.hash, tx.hash);
+    }
+
+    #[test]
+    fn test_transaction_signable_bytes() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let mut tx = random_transaction(1_000_000, 1_000_000);
+
+        let intermediate_tx = Transaction { hash: [0u8; 32], signature: [0u8; 64], ..tx.clone() };
+        assert_eq!(tx.signable_bytes(), Transaction::serialize(&intermediate_tx));
+
+        tx.sign(&keypair);
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+        let intermediate_tx = Transaction { hash: [0u8; 32], signature: [0u8; 64], ..tx.clone() };
+        assert_eq!(tx.signable_bytes(), Transaction::serialize(&intermediate_tx));
+    }
+
+    #[test]
+    fn test_transaction_new_deployment() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let txdata = DeployTransactionData {
+            contract_code: random_bytes::<10_000>().to_vec(),
+            contract_init_arguments: random_bytes::<128>().to_vec(),
+        };
+        let tx = Transaction::new_deployment(&keypair, txdata.contract_code.clone(), txdata.contract_init_arguments.clone(), 1_000_000, 2, 3);
+
+        assert_eq!(tx.from_address, keypair.public.to_bytes());
+        assert_eq!(tx.to_address, [0u8; 32]);
+        assert_eq!(tx.gas_limit, 1_000_000);
+        assert_eq!(tx.gas_price, 2);
+        assert_eq!(tx.n_txs_on_chain_from_address, 3);
+        assert_eq!(tx.data, DeployTransactionData::serialize(&txdata));
+        assert_eq!(tx.as_deployment_data().unwrap(), txdata);
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+
+        // test data which is not DeployTransactionData
+        let tx = Transaction { data: vec![1u8; 3], ..tx };
+        assert!(tx.as_deployment_data().is_err());
+    }
+
+    #[test]
+    fn test_transaction_with_call_data() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let call_data = CallData {
+            method_name: "transfer".to_string(),
+            arguments: random_bytes::<64>().to_vec(),
+        };
+        let tx = Transaction::with_call_data(&keypair, [1u8; 32], 100, 1_000_000, 2, 3, &call_data);
+
+        assert_eq!(tx.from_address, keypair.public.to_bytes());
+        assert_eq!(tx.to_address, [1u8; 32]);
+        assert_eq!(tx.value, 100);
+        assert_eq!(tx.data, CallData::serialize(&call_data));
+        assert_eq!(tx.as_call_data().unwrap(), call_data);
+        assert!(!tx.as_call_data().unwrap().is_init());
+        assert!(tx.verify_cryptographic_correctness().is_ok());
+
+        // test calling init method
+        let call_data = CallData::init(random_bytes::<64>().to_vec());
+        let tx = Transaction::with_call_data(&keypair, [1u8; 32], 0, 1_000_000, 2, 4, &call_data);
+        let deserialized = tx.as_call_data().unwrap();
+        assert!(deserialized.is_init());
+        assert_eq!(deserialized.method_name, "");
+        assert_eq!(deserialized, call_data);
+
+        // test data which is not CallData
+        let tx = Transaction { data: vec![1u8; 3], ..tx };
+        assert!(tx.as_call_data().is_err());
+    }
+
+    #[test]
+    fn test_transaction_kind() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+
+        let tx = Transaction { data: vec![], ..signed_transaction(&keypair) };
+        assert_eq!(tx.kind(), TransactionKind::Transfer);
+
+        let tx = Transaction::new_deployment(&keypair, random_bytes::<128>().to_vec(), vec![], 1_000_000, 1, 0);
+        assert_eq!(tx.kind(), TransactionKind::Deploy);
+
+        // DeployTransactionData which is not sent to the zero address
+        let tx = Transaction { to_address: [1u8; 32], ..tx };
+        assert_eq!(tx.kind(), TransactionKind::Call);
+
+        let call_data = CallData { method_name: "transfer".to_string(), arguments: vec![] };
+        let tx = Transaction::with_call_data(&keypair, [1u8; 32], 0, 1_000_000, 1, 1, &call_data);
+        assert_eq!(tx.kind(), TransactionKind::Call);
+
+        // data sent to the zero address which is not DeployTransactionData
+        let tx = Transaction { to_address: [0u8; 32], data: vec![1u8; 3], ..tx };
+        assert_eq!(tx.kind(), TransactionKind::Call);
+    }
+
+    #[test]
+    fn test_transaction_summary() {
+        let tx = random_transaction(1024, 1024);
+        let summary = TransactionSummary::from(&tx);
+        assert_eq!(summary.hash, tx.hash);
+        assert_eq!(summary.gas_price, tx.gas_price);
+        assert_eq!(summary.tip, tx.tip);
+        assert_eq!(summary.nonce, tx.n_txs_on_chain_from_address);
+        assert_eq!(summary.from_address, tx.from_address);
+
+        let serialized = TransactionSummary::serialize(&summary);
+        assert_eq!(serialized.len(), 32 + 8 + 8 + 8 + 32);
+        assert_eq!(TransactionSummary::deserialize(&serialized).unwrap(), summary);
+        assert!(TransactionSummary::deserialize(&serialized[..serialized.len()-1]).is_err());
+    }
+
     #[test]
     fn test_vec_transactions(){
         let transactions = random_transactions(100,100,0, 1024);
@@ -464,6 +1815,83 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_transaction_stream_reader() {
+        let transactions = random_transactions(100,100,0, 1024);
+        let serialized = Vec::<Transaction>::serialize(&transactions);
+
+        let mut reader = TransactionStreamReader::new(serialized.as_slice());
+        assert_eq!(reader.remaining().unwrap(), 100);
+        let deserialized: Vec<Transaction> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(transactions, deserialized);
+
+        // test empty Vec
+        let serialized = Vec::<Transaction>::serialize(&vec![]);
+        assert_eq!(TransactionStreamReader::new(serialized.as_slice()).count(), 0);
+    }
+
+    #[test]
+    fn test_transaction_stream_reader_error() {
+        // test empty stream
+        let empty_serialized :Vec<u8> = vec![];
+        let mut reader = TransactionStreamReader::new(empty_serialized.as_slice());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        // test stream missing last byte
+        let transactions = random_transactions(3,3,128, 128);
+        let serialized = Vec::<Transaction>::serialize(&transactions);
+        let mut reader = TransactionStreamReader::new(&serialized[..serialized.len()-1]);
+        assert_eq!(reader.next().unwrap().unwrap(), transactions[0]);
+        assert_eq!(reader.next().unwrap().unwrap(), transactions[1]);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        // test data length prefix larger than the stream
+        let mut serialized = Vec::<Transaction>::serialize(&transactions);
+        serialized[4 + 96..4 + 100].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut reader = TransactionStreamReader::new(serialized.as_slice());
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_transaction_length_prefixed() {
+        let transactions = random_transactions(3,3,0, 1024);
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        for tx in &transactions {
+            write_length_prefixed(&mut cursor, tx).unwrap();
+        }
+        let serialized = cursor.into_inner();
+        assert_eq!(serialized[..4], (transactions[0].size_of() as u32).to_le_bytes());
+
+        let mut cursor = std::io::Cursor::new(serialized);
+        for tx in &transactions {
+            assert_eq!(&read_length_prefixed(&mut cursor).unwrap(), tx);
+        }
+        assert!(read_length_prefixed(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_transaction_length_prefixed_error() {
+        let tx = random_transaction(0, 128);
+        let mut serialized = Vec::new();
+        write_length_prefixed(&mut serialized, &tx).unwrap();
+
+        // test missing last byte
+        let mut cursor = std::io::Cursor::new(&serialized[..serialized.len()-1]);
+        assert!(read_length_prefixed(&mut cursor).is_err());
+
+        // test length prefix shorter than the Transaction
+        let mut shorter = serialized.clone();
+        shorter[..4].copy_from_slice(&(tx.size_of() as u32 - 1).to_le_bytes());
+        assert!(read_length_prefixed(&mut std::io::Cursor::new(shorter)).is_err());
+
+        // test length prefix larger than the stream
+        let mut larger = serialized;
+        larger[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read_length_prefixed(&mut std::io::Cursor::new(larger)).is_err());
+    }
+
     #[test]
     fn test_transactiondatacontractdeployment() {
         let txdata = DeployTransactionData {
@@ -477,6 +1905,27 @@ mod test {
         assert_eq!(txdata.contract_init_arguments, deserialzied.contract_init_arguments);
     }
 
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_transactiondatacontractdeployment_compressed() {
+        // repetitive bytecode compresses well
+        let txdata = DeployTransactionData {
+            contract_code: b"\0asm\x01\0\0\0".iter().cycle().take(100_000).copied().collect(),
+            contract_init_arguments: random_bytes::<10_24>().to_vec(),
+        };
+        let compressed = txdata.serialize_compressed().unwrap();
+        assert!(compressed.len() < DeployTransactionData::serialize(&txdata).len() / 10);
+        assert_eq!(DeployTransactionData::deserialize_compressed(&compressed).unwrap(), txdata);
+
+        // random bytecode still round trips
+        let txdata = DeployTransactionData {
+            contract_code: random_bytes::<100_000>().to_vec(),
+            contract_init_arguments: vec![],
+        };
+        let compressed = txdata.serialize_compressed().unwrap();
+        assert_eq!(DeployTransactionData::deserialize_compressed(&compressed).unwrap(), txdata);
+    }
+
     #[test]
     fn test_transactiondatacontractdeployment_error() {
         // test empty vector
@@ -546,6 +1995,97 @@ mod test {
         }
     }
 
+    // Canonical serialization test vectors. Each is the exact byte layout of a value with fixed fields, as hex, so that a change
+    // to the wire format fails these tests rather than silently round-tripping.
+    const TRANSACTION_VECTOR: &str = concat!(
+        "0101010101010101010101010101010101010101010101010101010101010101", // from_address
+        "0202020202020202020202020202020202020202020202020202020202020202", // to_address
+        "e803000000000000", // value: 1000
+        "0a00000000000000", // tip: 10
+        "50c3000000000000", // gas_limit: 50000
+        "0800000000000000", // gas_price: 8
+        "03000000010203", // data: [1, 2, 3]
+        "0700000000000000", // n_txs_on_chain_from_address: 7
+        "0303030303030303030303030303030303030303030303030303030303030303", // hash
+        "04040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404040404", // signature
+    );
+
+    // The serialized `justify` of a BlockHeader is owned by hotstuff_rs_types, so the header vector is split around it.
+    const BLOCK_HEADER_VECTOR_BEFORE_JUSTIFY: &str = concat!(
+        "2a00000000000000", // app_id: 42
+        "0505050505050505050505050505050505050505050505050505050505050505", // hash
+        "6400000000000000", // height: 100
+    );
+    const BLOCK_HEADER_VECTOR_AFTER_JUSTIFY: &str = concat!(
+        "0606060606060606060606060606060606060606060606060606060606060606", // data_hash
+        "0200000000000000", // version_number: 2
+        "00105e5f", // timestamp: 1600000000
+        "0707070707070707070707070707070707070707070707070707070707070707", // txs_hash
+        "0808080808080808080808080808080808080808080808080808080808080808", // state_hash
+        "0909090909090909090909090909090909090909090909090909090909090909", // receipts_hash
+    );
+
+    const RECEIPT_VECTOR: &str = concat!(
+        "00", // status_code: Success
+        "0852000000000000", // gas_consumed: 21000
+        "020000000909", // return_value: [9, 9]
+        "01000000", // one event
+        "0100000074", // topic: "t"
+        "0100000076", // value: "v"
+    );
+
+    #[test]
+    fn test_serialization_vectors() {
+        let tx_bytes = Hex::decode(TRANSACTION_VECTOR).unwrap();
+        let tx = Transaction::deserialize(&tx_bytes).unwrap();
+        assert_eq!(tx.from_address, [1u8; 32]);
+        assert_eq!(tx.to_address, [2u8; 32]);
+        assert_eq!(tx.value, 1000);
+        assert_eq!(tx.tip, 10);
+        assert_eq!(tx.gas_limit, 50000);
+        assert_eq!(tx.gas_price, 8);
+        assert_eq!(tx.data, vec![1u8, 2, 3]);
+        assert_eq!(tx.n_txs_on_chain_from_address, 7);
+        assert_eq!(tx.hash, [3u8; 32]);
+        assert_eq!(tx.signature, [4u8; 64]);
+        assert_eq!(Transaction::serialize(&tx), tx_bytes);
+
+        let justify = hotstuff_rs_types::messages::QuorumCertificate {
+            view_number: 99,
+            block_hash: [1u8; 32],
+            sigs: hotstuff_rs_types::messages::SignatureSet {
+                signatures: vec![],
+                count_some: 0,
+            },
+        };
+        let header_bytes = [
+            Hex::decode(BLOCK_HEADER_VECTOR_BEFORE_JUSTIFY).unwrap(),
+            borsh::BorshSerialize::try_to_vec(&justify).unwrap(),
+            Hex::decode(BLOCK_HEADER_VECTOR_AFTER_JUSTIFY).unwrap(),
+        ].concat();
+        let header = BlockHeader::deserialize(&header_bytes).unwrap();
+        assert_eq!(header.app_id, 42);
+        assert_eq!(header.hash, [5u8; 32]);
+        assert_eq!(header.height, 100);
+        assert_eq!(header.justify.view_number, 99);
+        assert_eq!(header.justify.block_hash, [1u8; 32]);
+        assert_eq!(header.data_hash, [6u8; 32]);
+        assert_eq!(header.version_number, 2);
+        assert_eq!(header.timestamp, 1_600_000_000);
+        assert_eq!(header.txs_hash, [7u8; 32]);
+        assert_eq!(header.state_hash, [8u8; 32]);
+        assert_eq!(header.receipts_hash, [9u8; 32]);
+        assert_eq!(BlockHeader::serialize(&header), header_bytes);
+
+        let receipt_bytes = Hex::decode(RECEIPT_VECTOR).unwrap();
+        let receipt = Receipt::deserialize(&receipt_bytes).unwrap();
+        assert_eq!(receipt.status_code, ReceiptStatusCode::Success);
+        assert_eq!(receipt.gas_consumed, 21000);
+        assert_eq!(receipt.return_value, vec![9u8, 9]);
+        assert_eq!(receipt.events, vec![Event { topic: b"t".to_vec(), value: b"v".to_vec() }]);
+        assert_eq!(Receipt::serialize(&receipt), receipt_bytes);
+    }
+
     #[test]
     fn test_receipt_error(){
         // test empty vector
@@ -564,6 +2104,154 @@ mod test {
         assert!(Receipt::deserialize(&serialized_missing_last_byte).is_err());
     }
 
+    #[test]
+    fn test_receipt_and_event_versioned() {
+        let receipt = random_receipt(3, 3, 0, 128);
+        let serialized = receipt.serialize_versioned();
+        assert_eq!(serialized[0], SERIALIZATION_VERSION);
+        assert_eq!(serialized[1..], Receipt::serialize(&receipt));
+        assert_eq!(Receipt::deserialize_versioned(&serialized).unwrap(), receipt);
+
+        let event = receipt.events[0].clone();
+        let serialized = event.serialize_versioned();
+        assert_eq!(serialized[0], SERIALIZATION_VERSION);
+        assert_eq!(serialized[1..], Event::serialize(&event));
+        assert_eq!(Event::deserialize_versioned(&serialized).unwrap(), event);
+    }
+
+    #[test]
+    fn test_receipt_and_event_versioned_error() {
+        // test empty vector
+        assert!(Receipt::deserialize_versioned(&[]).is_err());
+        assert!(Event::deserialize_versioned(&[]).is_err());
+
+        // test unknown versions
+        let receipt = random_receipt(3, 3, 0, 128);
+        let mut serialized = receipt.serialize_versioned();
+        for version in [0, 2, u8::MAX] {
+            serialized[0] = version;
+            assert!(Receipt::deserialize_versioned(&serialized).is_err());
+        }
+
+        let mut serialized = receipt.events[0].serialize_versioned();
+        serialized[0] = 2;
+        assert!(Event::deserialize_versioned(&serialized).is_err());
+
+        // test unversioned serialization
+        assert!(Receipt::deserialize_versioned(&Receipt::serialize(&receipt)).is_err());
+    }
+
+    #[test]
+    fn test_event_deserialize_ref() {
+        let events = random_events(10, 10, 0, 128);
+        let serialized: Vec<u8> = events.iter().flat_map(Event::serialize).collect();
+
+        let mut buf = serialized.as_slice();
+        for event in &events {
+            let (event_ref, size) = Event::deserialize_ref(buf).unwrap();
+            assert_eq!(event_ref.topic, event.topic.as_slice());
+            assert_eq!(event_ref.value, event.value.as_slice());
+            assert_eq!(size, event.size_of());
+            assert_eq!(Event::from(event_ref), *event);
+            buf = &buf[size..];
+        }
+        assert!(buf.is_empty());
+
+        // truncated event
+        let serialized = Event::serialize(&events[0]);
+        assert!(Event::deserialize_ref(&serialized[..serialized.len()-1]).is_err());
+        assert!(Event::deserialize_ref(&[]).is_err());
+        assert_eq!(
+            Event::deserialize_ref(&[0, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+            (EventRef { topic: &[], value: &[] }, 8)
+        );
+    }
+
+    #[test]
+    fn test_receipt_build_topic_index() {
+        let event = |topic: &[u8]| Event { topic: topic.to_vec(), value: random_bytes::<8>().to_vec() };
+        let receipt = Receipt {
+            status_code: ReceiptStatusCode::Success,
+            gas_consumed: 1,
+            return_value: vec![],
+            events: vec![event(b"transfer"), event(b"mint"), event(b"transfer"), event(b""), event(b"transfer")],
+        };
+
+        let index = receipt.build_topic_index();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index[&b"transfer".to_vec()], vec![0, 2, 4]);
+        assert_eq!(index[&b"mint".to_vec()], vec![1]);
+        assert_eq!(index[&vec![]], vec![3]);
+        assert!(!index.contains_key(b"burn".as_slice()));
+
+        let empty = Receipt { events: vec![], ..receipt };
+        assert!(empty.build_topic_index().is_empty());
+    }
+
+    #[test]
+    fn test_receipt_deserialize_lenient_many_events() {
+        let receipt = Receipt {
+            status_code: ReceiptStatusCode::Success,
+            gas_consumed: 1,
+            return_value: vec![],
+            events: (0..10_000u32).map(|i| Event { topic: vec![], value: i.to_le_bytes().to_vec() }).collect(),
+        };
+        let serialized = Receipt::serialize(&receipt);
+        let (deserialized, error) = Receipt::deserialize_lenient(&serialized).unwrap();
+        assert!(error.is_none());
+        assert_eq!(deserialized, Receipt::deserialize(&serialized).unwrap());
+
+        // a number of events which the remaining bytes cannot hold
+        let mut serialized = Receipt::serialize(&Receipt { events: vec![], ..receipt });
+        let len = serialized.len();
+        serialized[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        let (deserialized, error) = Receipt::deserialize_lenient(&serialized).unwrap();
+        assert!(error.is_some());
+        assert!(deserialized.events.is_empty());
+    }
+
+    #[test]
+    fn test_receipt_deserialize_lenient() {
+        let r = Receipt{
+            gas_consumed:102,
+            status_code: ReceiptStatusCode::Success,
+            return_value: random_bytes::<16>().to_vec(),
+            events: random_events(10,10,1, 1024),
+        };
+        let serialized = Receipt::serialize(&r);
+        let (deserialized, error) = Receipt::deserialize_lenient(&serialized).unwrap();
+        assert_eq!(r, deserialized);
+        assert!(error.is_none());
+
+        // test by truncating the last event
+        let serialized_missing_last_byte = serialized[..serialized.len()-1].to_vec();
+        let (deserialized, error) = Receipt::deserialize_lenient(&serialized_missing_last_byte).unwrap();
+        assert_eq!(r.status_code, deserialized.status_code);
+        assert_eq!(r.gas_consumed, deserialized.gas_consumed);
+        assert_eq!(r.return_value, deserialized.return_value);
+        assert_eq!(r.events[..9], deserialized.events[..]);
+        assert!(error.is_some());
+
+        // test by truncating the fields before events
+        assert!(Receipt::deserialize_lenient(&serialized[..10]).is_err());
+    }
+
+    #[test]
+    fn test_envelope() {
+        let receipt = random_receipt(3, 3, 0, 128);
+        let envelope = Envelope::wrap_receipt(&receipt);
+        assert_eq!(envelope.type_tag, Envelope::RECEIPT_TYPE_TAG);
+
+        let serialized = Envelope::serialize(&envelope);
+        let deserialized = Envelope::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, envelope);
+        assert_eq!(deserialized.unwrap_receipt().unwrap(), receipt);
+
+        // test unwrapping as the wrong type
+        assert!(deserialized.unwrap_transaction().is_err());
+        assert!(deserialized.unwrap_block().is_err());
+    }
+
     #[test]
     fn test_merkleproof(){
         let p = MerkleProof{
@@ -586,6 +2274,74 @@ mod test {
         assert_eq!(p.proof, deserialized.proof);
     }
 
+    #[test]
+    fn test_merkleproof_verify() {
+        let transactions = random_transactions(7, 7, 0, 128);
+        let (leaves, root_hash, proof) = crypto::merkle_proof::<Transaction, Transaction>(&transactions, 3).ok().unwrap();
+        let mut merkle_proof = MerkleProof {
+            root_hash,
+            total_leaves_count: leaves.len(),
+            leaf_indices: vec![3],
+            leaf_hashes: vec![leaves[3]],
+            proof,
+        };
+        assert!(merkle_proof.verify());
+
+        merkle_proof.leaf_hashes = vec![leaves[4]];
+        assert!(!merkle_proof.verify());
+
+        merkle_proof.leaf_hashes = vec![leaves[3]];
+        merkle_proof.proof = vec![1u8; 3];
+        assert!(!merkle_proof.verify());
+    }
+
+    #[test]
+    fn test_merkleproof_build() {
+        let transactions = random_transactions(7, 7, 0, 128);
+        let (leaves, root_hash, proof) = crypto::merkle_proof::<Transaction, Transaction>(&transactions, 2).ok().unwrap();
+
+        let merkle_proof = MerkleProof::build(&leaves, &[2]);
+        assert_eq!(merkle_proof, MerkleProof {
+            root_hash,
+            total_leaves_count: 7,
+            leaf_indices: vec![2],
+            leaf_hashes: vec![leaves[2]],
+            proof,
+        });
+        assert!(merkle_proof.verify());
+
+        let merkle_proof = MerkleProof::build(&leaves, &[0, 6]);
+        assert_eq!(merkle_proof.leaf_hashes, vec![leaves[0], leaves[6]]);
+        assert!(merkle_proof.verify());
+    }
+
+    #[test]
+    fn test_verify_transaction_inclusion() {
+        let mut block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(7, 7, 0, 128),
+            receipts: random_receipts(7, 7, 1,1,0, 128),
+        };
+        block.header.txs_hash = crypto::merkle_root::<Transaction, Transaction>(&block.transactions);
+
+        let (leaves, root_hash, proof) = crypto::merkle_proof::<Transaction, Transaction>(&block.transactions, 5).ok().unwrap();
+        let merkle_proof = MerkleProof {
+            root_hash,
+            total_leaves_count: leaves.len(),
+            leaf_indices: vec![5],
+            leaf_hashes: vec![leaves[5]],
+            proof,
+        };
+        assert!(verify_transaction_inclusion(&block.transactions[5], &merkle_proof, &block.header));
+
+        // transaction which is not proven
+        assert!(!verify_transaction_inclusion(&block.transactions[4], &merkle_proof, &block.header));
+
+        // header of another block
+        let other_header = random_blockheader();
+        assert!(!verify_transaction_inclusion(&block.transactions[5], &merkle_proof, &other_header));
+    }
+
     #[test]
     fn test_merkleproof_error() {
         // test empty vector
@@ -645,8 +2401,174 @@ mod test {
         assert!(StateProofs::deserialize(&serialized).is_err());
     }
 
+    #[test]
+    fn test_proofs_serialize_to_slice() {
+        let leaves: Vec<Sha256Hash> = (0..10).map(|_| random_bytes::<32>()).collect();
+        let merkle_proofs = vec![
+            MerkleProof::build(&leaves, &[3]),
+            MerkleProof::build(&leaves, &[0, 5, 9]),
+            MerkleProof::build(&[], &[]),
+        ];
+        let mut buf = vec![0u8; merkle_proofs.iter().map(MerkleProof::size_of).sum()];
+        let mut offset = 0;
+        for proof in &merkle_proofs {
+            let serialized = MerkleProof::serialize(proof);
+            assert_eq!(proof.size_of(), serialized.len());
+            let written = proof.serialize_to_slice(&mut buf[offset..]);
+            assert_eq!(buf[offset..offset + written], serialized[..]);
+            offset += written;
+        }
+        assert_eq!(offset, buf.len());
+
+        let spfs = StateProofs {
+            root_hash : random_bytes::<32>(),
+            items : vec![
+                (random_bytes::<21>().to_vec(), Some(random_bytes::<32>().to_vec())),
+                (random_bytes::<23>().to_vec(), None),
+            ],
+            proof : vec![random_bytes::<56>().to_vec(), vec![]],
+        };
+        let serialized = StateProofs::serialize(&spfs);
+        assert_eq!(spfs.size_of(), serialized.len());
+        let mut buf = vec![0u8; serialized.len() + 10];
+        assert_eq!(spfs.serialize_to_slice(&mut buf), serialized.len());
+        assert_eq!(buf[..serialized.len()], serialized[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merkleproof_serialize_to_slice_short_buffer() {
+        let proof = MerkleProof::build(&[random_bytes::<32>(), random_bytes::<32>()], &[1]);
+        let mut buf = vec![0u8; proof.size_of() - 1];
+        proof.serialize_to_slice(&mut buf);
+    }
+
+    #[test]
+    fn test_stateproofs_proof_stats() {
+        let entries = vec![
+            (b"alice".to_vec(), b"100".to_vec()),
+            (b"bob".to_vec(), b"200".to_vec()),
+            (b"carol".to_vec(), b"300".to_vec()),
+        ];
+        let (db, root_hash) = state_trie(&entries);
+        let keys = vec![b"alice".to_vec(), b"dave".to_vec()];
+        let proof = trie_db::proof::generate_proof::<_, NoExtensionLayout, _, _>(&db, &root_hash, &keys).unwrap();
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"100".to_vec())), (b"dave".to_vec(), None)],
+            proof: proof.clone(),
+        };
+
+        let stats = spfs.proof_stats();
+        assert_eq!(stats.node_count, proof.len());
+        assert_eq!(stats.total_bytes, proof.iter().map(|node| node.len()).sum::<usize>());
+        assert_eq!(stats.item_count, 2);
+
+        let empty = StateProofs { root_hash, items: vec![], proof: vec![] };
+        assert_eq!(empty.proof_stats(), ProofStats::default());
+    }
+
+    #[test]
+    fn test_stateproofs_verify() {
+        let entries = vec![
+            (b"alice".to_vec(), b"100".to_vec()),
+            (b"bob".to_vec(), b"200".to_vec()),
+            (b"carol".to_vec(), b"300".to_vec()),
+        ];
+        let (db, root_hash) = state_trie(&entries);
+        let keys = vec![b"alice".to_vec(), b"dave".to_vec()];
+        let proof = trie_db::proof::generate_proof::<_, NoExtensionLayout, _, _>(&db, &root_hash, &keys).unwrap();
+
+        // present key with matching value, absent key asserted absent
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"100".to_vec())), (b"dave".to_vec(), None)],
+            proof: proof.clone(),
+        };
+        assert_eq!(spfs.verify::<NoExtensionLayout>(), Ok(true));
+
+        // present key with wrong value
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"101".to_vec())), (b"dave".to_vec(), None)],
+            proof: proof.clone(),
+        };
+        assert_eq!(spfs.verify::<NoExtensionLayout>(), Ok(false));
+
+        // present key asserted absent
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), None), (b"dave".to_vec(), None)],
+            proof,
+        };
+        assert_eq!(spfs.verify::<NoExtensionLayout>(), Ok(false));
+    }
+
+    #[test]
+    fn test_stateproofs_verify_against() {
+        let entries = vec![
+            (b"alice".to_vec(), b"100".to_vec()),
+            (b"bob".to_vec(), b"200".to_vec()),
+        ];
+        let (db, root_hash) = state_trie(&entries);
+        let keys = vec![b"alice".to_vec()];
+        let proof = trie_db::proof::generate_proof::<_, NoExtensionLayout, _, _>(&db, &root_hash, &keys).unwrap();
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"100".to_vec()))],
+            proof,
+        };
+
+        // matching external root
+        assert_eq!(spfs.verify_against::<NoExtensionLayout>(&root_hash), Ok(true));
+
+        // conflicting external root
+        let other_root_hash = [1u8; 32];
+        assert_eq!(spfs.verify_against::<NoExtensionLayout>(&other_root_hash), Err(StateProofError::ConflictingRootHash(other_root_hash)));
+    }
+
+    #[test]
+    fn test_stateproofs_verify_error() {
+        let entries = vec![(b"alice".to_vec(), b"100".to_vec())];
+        let (_, root_hash) = state_trie(&entries);
+
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"100".to_vec()))],
+            proof: vec![random_bytes::<57>().to_vec()],
+        };
+        assert!(spfs.verify::<NoExtensionLayout>().is_err());
+
+        let spfs = StateProofs {
+            root_hash,
+            items: vec![(b"alice".to_vec(), Some(b"100".to_vec()))],
+            proof: vec![],
+        };
+        assert_eq!(spfs.verify::<NoExtensionLayout>(), Err(StateProofError::IncompleteProof));
+    }
+
     #[test]
     fn test_generics(){
+        // bool
+        for the_bool in [true, false] {
+            let serialized = bool::serialize(&the_bool);
+            let deserialized = bool::deserialize(&serialized).unwrap();
+            assert_eq!(the_bool, deserialized);
+        }
+        assert!(bool::deserialize(&[2]).is_err());
+
+        // u8
+        let the_u8 = 123_u8;
+        let serialized = u8::serialize(&the_u8);
+        let deserialized = u8::deserialize(&serialized).unwrap();
+        assert_eq!(the_u8, deserialized);
+
+        // u16
+        let the_u16 = 12345_u16;
+        let serialized = u16::serialize(&the_u16);
+        let deserialized = u16::deserialize(&serialized).unwrap();
+        assert_eq!(the_u16, deserialized);
+
         // u32
         let the_u32 = 1234_u32;
         let serialized = u32::serialize(&the_u32);
@@ -659,6 +2581,38 @@ mod test {
         let deserialized = u64::deserialize(&serialized).unwrap();
         assert_eq!(the_u64, deserialized);
 
+        // u128
+        let the_u128 = u128::MAX - 1234;
+        let serialized = u128::serialize(&the_u128);
+        let deserialized = u128::deserialize(&serialized).unwrap();
+        assert_eq!(the_u128, deserialized);
+
+        // i32
+        let the_i32 = -1234_i32;
+        let serialized = i32::serialize(&the_i32);
+        let deserialized = i32::deserialize(&serialized).unwrap();
+        assert_eq!(the_i32, deserialized);
+
+        // i64
+        let the_i64 = -1234123412341234_i64;
+        let serialized = i64::serialize(&the_i64);
+        let deserialized = i64::deserialize(&serialized).unwrap();
+        assert_eq!(the_i64, deserialized);
+
+        // Fixed-size arrays
+        let hash: Sha256Hash = random_bytes::<32>();
+        let serialized = Sha256Hash::serialize(&hash);
+        assert_eq!(serialized, hash.to_vec());
+        assert_eq!(Sha256Hash::deserialize(&serialized).unwrap(), hash);
+        assert!(PublicAddress::deserialize(&serialized[1..]).is_err());
+        assert!(PublicAddress::deserialize(&[serialized.clone(), vec![0]].concat()).is_err());
+
+        let signature: crypto::Signature = random_bytes::<64>();
+        let serialized = crypto::Signature::serialize(&signature);
+        assert_eq!(serialized, signature.to_vec());
+        assert_eq!(crypto::Signature::deserialize(&serialized).unwrap(), signature);
+        assert!(crypto::Signature::deserialize(&serialized[..63]).is_err());
+
         // Vec<u8>
         let vs = vec![];
         let serialized = Vec::<u8>::serialize(&vs);
@@ -707,32 +2661,445 @@ mod test {
         let deserialized = <(Vec::<u8>, Option::<Vec::<u8>>)>::deserialize(&serialized).unwrap();
         assert_eq!(vs_some, deserialized);
 
+        // String
+        let the_string = String::new();
+        let serialized = String::serialize(&the_string);
+        let deserialized = String::deserialize(&serialized).unwrap();
+        assert_eq!(the_string, deserialized);
+
+        let the_string = "ParallelChain パラレルチェーン 🦀".to_string();
+        let serialized = String::serialize(&the_string);
+        assert_eq!(serialized[..4], (the_string.len() as u32).to_le_bytes());
+        let deserialized = String::deserialize(&serialized).unwrap();
+        assert_eq!(the_string, deserialized);
+
+        // String with invalid UTF-8
+        let mut serialized = String::serialize(&the_string);
+        serialized[4] = 0xff;
+        assert!(String::deserialize(&serialized).is_err());
+
     }
 
     #[test]
     fn test_status_codes() {
-        [
-            ReceiptStatusCode::Success,
-            ReceiptStatusCode::WrongNonce,
-            ReceiptStatusCode::NotEnoughBalanceForGasLimit,
-            ReceiptStatusCode::NotEnoughBalanceForTransfer,
-            ReceiptStatusCode::PreExecutionGasExhausted,
-            ReceiptStatusCode::DisallowedOpcode,
-            ReceiptStatusCode::CannotCompile,
-            ReceiptStatusCode::NoExportedContractMethod,
-            ReceiptStatusCode::OtherDeployError,
-            ReceiptStatusCode::ExecutionProperGasExhausted,
-            ReceiptStatusCode::RuntimeError,
-            ReceiptStatusCode::InternalExecutionProperGasExhaustion,
-            ReceiptStatusCode::InternalRuntimeError,
-            ReceiptStatusCode::InternalNotEnoughBalanceForTransfer,
-            ReceiptStatusCode::Else,
-        ].to_vec().iter().for_each(|c|{
+        ReceiptStatusCode::all().iter().for_each(|c|{
             let code = c.clone();
             let byte: u8 = code.clone().into();
             let code_from_byte: ReceiptStatusCode = ReceiptStatusCode::try_from(byte).unwrap();
             assert_eq!(code, code_from_byte);
         });
+
+        // all() contains every status code that a byte can be converted into, in order of their bytes
+        let codes_from_bytes: Vec<ReceiptStatusCode> = (0..=u8::MAX)
+            .filter_map(|b| ReceiptStatusCode::try_from(b).ok())
+            .collect();
+        assert_eq!(ReceiptStatusCode::all().to_vec(), codes_from_bytes);
+    }
+
+    #[test]
+    fn test_status_code_class() {
+        assert_eq!(ReceiptStatusCode::Success.class(), ReceiptStatusClass::Success);
+        assert_eq!(ReceiptStatusCode::WrongNonce.class(), ReceiptStatusClass::PreInclusionDecision);
+        assert_eq!(ReceiptStatusCode::PreExecutionGasExhausted.class(), ReceiptStatusClass::PreInclusionDecision);
+        assert_eq!(ReceiptStatusCode::DisallowedOpcode.class(), ReceiptStatusClass::Deploy);
+        assert_eq!(ReceiptStatusCode::OtherDeployError.class(), ReceiptStatusClass::Deploy);
+        assert_eq!(ReceiptStatusCode::ExecutionProperGasExhausted.class(), ReceiptStatusClass::EtoC);
+        assert_eq!(ReceiptStatusCode::RuntimeError.class(), ReceiptStatusClass::EtoC);
+        assert_eq!(ReceiptStatusCode::InternalExecutionProperGasExhaustion.class(), ReceiptStatusClass::InternalTransaction);
+        assert_eq!(ReceiptStatusCode::InternalNotEnoughBalanceForTransfer.class(), ReceiptStatusClass::InternalTransaction);
+        assert_eq!(ReceiptStatusCode::Else.class(), ReceiptStatusClass::Miscellaneous);
+    }
+
+    #[test]
+    fn test_status_code_error_predicates() {
+        assert!(!ReceiptStatusCode::Success.is_deploy_error());
+        assert!(!ReceiptStatusCode::Success.is_execution_error());
+        assert!(!ReceiptStatusCode::WrongNonce.is_deploy_error());
+        assert!(!ReceiptStatusCode::WrongNonce.is_execution_error());
+
+        assert!(ReceiptStatusCode::DisallowedOpcode.is_deploy_error());
+        assert!(ReceiptStatusCode::CannotCompile.is_deploy_error());
+        assert!(ReceiptStatusCode::NoExportedContractMethod.is_deploy_error());
+        assert!(ReceiptStatusCode::OtherDeployError.is_deploy_error());
+        assert!(!ReceiptStatusCode::OtherDeployError.is_execution_error());
+
+        assert!(ReceiptStatusCode::ExecutionProperGasExhausted.is_execution_error());
+        assert!(ReceiptStatusCode::RuntimeError.is_execution_error());
+        assert!(ReceiptStatusCode::InternalExecutionProperGasExhaustion.is_execution_error());
+        assert!(ReceiptStatusCode::InternalRuntimeError.is_execution_error());
+        assert!(!ReceiptStatusCode::InternalNotEnoughBalanceForTransfer.is_execution_error());
+        assert!(!ReceiptStatusCode::RuntimeError.is_deploy_error());
+
+        assert!(!ReceiptStatusCode::Else.is_deploy_error());
+        assert!(!ReceiptStatusCode::Else.is_execution_error());
+    }
+
+    #[test]
+    fn test_status_code_describe() {
+        ReceiptStatusCode::all().iter().for_each(|c|{
+            let info = c.describe();
+            assert_eq!(info.code, Into::<u8>::into(c.clone()));
+            assert_eq!(info.name, format!("{:?}", c));
+            assert_eq!(info.class, c.class());
+            assert_eq!(info.is_retryable, c.is_retryable());
+            assert_eq!(info.is_includable, c.is_includable());
+        });
+    }
+
+    #[test]
+    fn test_status_code_ordering() {
+        let mut codes: Vec<ReceiptStatusCode> = (0..=u8::MAX).rev()
+            .filter_map(|b| ReceiptStatusCode::try_from(b).ok())
+            .collect();
+        codes.sort();
+        let bytes: Vec<u8> = codes.iter().map(|c| c.clone().into()).collect();
+        let mut sorted_bytes = bytes.clone();
+        sorted_bytes.sort_unstable();
+        assert_eq!(bytes, sorted_bytes);
+
+        assert_eq!(codes.first(), Some(&ReceiptStatusCode::Success));
+        assert_eq!(codes.last(), Some(&ReceiptStatusCode::Else));
+        assert!(ReceiptStatusCode::Success < ReceiptStatusCode::WrongNonce);
+        assert!(ReceiptStatusCode::RuntimeError > ReceiptStatusCode::OtherDeployError);
+    }
+
+    #[test]
+    fn test_status_code_histogram() {
+        let codes = vec![
+            ReceiptStatusCode::Success,
+            ReceiptStatusCode::RuntimeError,
+            ReceiptStatusCode::Success,
+            ReceiptStatusCode::WrongNonce,
+            ReceiptStatusCode::RuntimeError,
+            ReceiptStatusCode::Success,
+        ];
+        let histogram = crate::receipt_status_codes::histogram(codes.into_iter());
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram[&ReceiptStatusCode::Success], 3);
+        assert_eq!(histogram[&ReceiptStatusCode::RuntimeError], 2);
+        assert_eq!(histogram[&ReceiptStatusCode::WrongNonce], 1);
+        assert!(!histogram.contains_key(&ReceiptStatusCode::Else));
+
+        // ordered by status code
+        let keys: Vec<&ReceiptStatusCode> = histogram.keys().collect();
+        assert_eq!(keys, vec![&ReceiptStatusCode::Success, &ReceiptStatusCode::WrongNonce, &ReceiptStatusCode::RuntimeError]);
+
+        assert!(crate::receipt_status_codes::histogram(std::iter::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_status_code_display() {
+        assert_eq!(ReceiptStatusCode::Success.to_string(), "success");
+        assert_eq!(ReceiptStatusCode::RuntimeError.to_string(), "runtime error during execution");
+        assert_eq!(ReceiptStatusCode::Else.to_string(), "other error");
+
+        let messages: std::collections::HashSet<String> = (0..=u8::MAX)
+            .filter_map(|b| ReceiptStatusCode::try_from(b).ok())
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(messages.len(), 15);
+    }
+
+    #[test]
+    fn test_double_sha256() {
+        assert_eq!(
+            Hex::encode(crypto::double_sha256(b"hello")).to_string(),
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50"
+        );
+        assert_eq!(
+            Hex::encode(crypto::double_sha256(b"")).to_string(),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+    }
+
+    #[test]
+    fn test_short_address() {
+        let addr = random_bytes::<32>();
+        let encoded = Base64URL::encode(addr).to_string();
+        let short = crypto::short_address(&addr);
+        assert_eq!(short, format!("{}…{}", &encoded[..4], &encoded[encoded.len()-4..]));
+        assert_eq!(short.chars().count(), 9);
+
+        assert_eq!(crypto::short_address(&[0u8; 32]), "AAAA…AAAA");
+        assert_ne!(crypto::short_address(&random_bytes::<32>()), crypto::short_address(&random_bytes::<32>()));
+    }
+
+    #[test]
+    fn test_sort_addresses() {
+        let mut addrs: Vec<PublicAddress> = vec![[3u8; 32], [1u8; 32], [255u8; 32], [0u8; 32], [1u8; 32]];
+        crypto::sort_addresses(&mut addrs);
+        assert_eq!(addrs, vec![[0u8; 32], [1u8; 32], [1u8; 32], [3u8; 32], [255u8; 32]]);
+
+        // ordering is lexicographic, starting from the first byte
+        let mut low_first_byte = [255u8; 32];
+        low_first_byte[0] = 0;
+        let mut addrs: Vec<PublicAddress> = vec![[1u8; 32], low_first_byte];
+        crypto::sort_addresses(&mut addrs);
+        assert_eq!(addrs, vec![low_first_byte, [1u8; 32]]);
+    }
+
+    #[test]
+    fn test_validator_set_hash() {
+        let addrs: Vec<PublicAddress> = (0..10).map(|_| random_bytes::<32>()).collect();
+        let mut reversed_addrs = addrs.clone();
+        reversed_addrs.reverse();
+        let mut sorted_addrs = addrs.clone();
+        crypto::sort_addresses(&mut sorted_addrs);
+
+        let hash = crypto::validator_set_hash(&addrs);
+        assert_eq!(hash, crypto::validator_set_hash(&reversed_addrs));
+        assert_eq!(hash, crypto::validator_set_hash(&sorted_addrs));
+        assert_ne!(hash, crypto::validator_set_hash(&addrs[1..]));
+    }
+
+    #[test]
+    fn test_crypto_from_bytes() {
+        let bytes = random_bytes::<32>();
+        assert_eq!(crypto::public_address_from_bytes(&bytes), Ok(bytes));
+        assert_eq!(crypto::sha256_hash_from_bytes(&bytes), Ok(bytes));
+
+        assert_eq!(crypto::public_address_from_bytes(&bytes[..31]), Err(IncorrectLengthError { expected: 32, actual: 31 }));
+        assert_eq!(crypto::sha256_hash_from_bytes(&[bytes.to_vec(), vec![0]].concat()), Err(IncorrectLengthError { expected: 32, actual: 33 }));
+        assert_eq!(crypto::sha256_hash_from_bytes(&[]), Err(IncorrectLengthError { expected: 32, actual: 0 }));
+    }
+
+    #[test]
+    fn test_sign_batch() {
+        use ed25519_dalek::Verifier;
+
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let messages = [random_bytes_dyn(0), random_bytes_dyn(32), random_bytes_dyn(1024)];
+        let message_slices: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+
+        let signatures = crypto::sign_batch(&keypair, &message_slices);
+        assert_eq!(signatures.len(), messages.len());
+        for (message, signature) in messages.iter().zip(signatures.iter()) {
+            let signature = ed25519_dalek::Signature::from_bytes(signature).unwrap();
+            assert!(keypair.public.verify(message, &signature).is_ok());
+        }
+
+        assert!(crypto::sign_batch(&keypair, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keypair = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng);
+        let secret: crypto::SecretKey = keypair.secret.to_bytes();
+        let public: PublicAddress = keypair.public.to_bytes();
+        let message = random_bytes::<100>();
+
+        let signature = crypto::sign(&secret, &message);
+        assert_eq!(signature, crypto::sign_batch(&keypair, &[&message])[0]);
+        assert!(crypto::verify(&public, &message, &signature));
+
+        // wrong message
+        assert!(!crypto::verify(&public, &message[1..], &signature));
+        // wrong signature
+        let mut wrong_signature = signature;
+        wrong_signature[0] ^= 1;
+        assert!(!crypto::verify(&public, &message, &wrong_signature));
+        // wrong public key
+        let other_public = ed25519_dalek::Keypair::generate(&mut rand::rngs::OsRng).public.to_bytes();
+        assert!(!crypto::verify(&other_public, &message, &signature));
+        // malformed signature
+        assert!(!crypto::verify(&public, &message, &[255u8; 64]));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let hash = random_bytes::<32>();
+        assert!(crypto::ct_eq(&hash, &hash));
+
+        let mut other_hash = hash;
+        other_hash[31] ^= 1;
+        assert!(!crypto::ct_eq(&hash, &other_hash));
+
+        // different lengths
+        assert!(!crypto::ct_eq(&hash, &hash[..31]));
+        assert!(crypto::ct_eq(&[], &[]));
+    }
+
+    #[test]
+    fn test_ct_eq_hash_and_signature() {
+        let hash: Sha256Hash = random_bytes::<32>();
+        let mut other_hash = hash;
+        other_hash[0] ^= 0x80;
+        for (a, b) in [(hash, hash), (hash, other_hash), (other_hash, hash)] {
+            assert_eq!(crypto::ct_eq_hash(&a, &b), a == b);
+        }
+
+        let signature: crypto::Signature = random_bytes::<64>();
+        let mut other_signature = signature;
+        other_signature[63] ^= 1;
+        for (a, b) in [(signature, signature), (signature, other_signature), (other_signature, signature)] {
+            assert_eq!(crypto::ct_eq_signature(&a, &b), a == b);
+        }
+    }
+
+    #[test]
+    fn test_contract_address() {
+        let deployer = random_bytes::<32>();
+        assert_eq!(crypto::contract_address(&deployer, 1), crypto::contract_address(&deployer, 1));
+        assert_ne!(crypto::contract_address(&deployer, 1), crypto::contract_address(&deployer, 2));
+        assert_ne!(crypto::contract_address(&deployer, 1), crypto::contract_address(&random_bytes::<32>(), 1));
+
+        let preimage = [deployer.to_vec(), 1u64.to_le_bytes().to_vec()].concat();
+        let expected: PublicAddress = Sha256::digest(&preimage).into();
+        assert_eq!(crypto::contract_address(&deployer, 1), expected);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(*Hex::encode([]), "");
+        assert_eq!(*Hex::encode([0x00, 0x01, 0xab, 0xff]), "0001abff");
+
+        let bytes = random_bytes::<32>();
+        let hex = Hex::encode(bytes);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(Hex::decode(&*hex).unwrap(), bytes.to_vec());
+        assert_eq!(Hex::decode(&format!("0x{}", *hex)).unwrap(), bytes.to_vec());
+        assert_eq!(Hex::decode(&hex.to_uppercase()).unwrap(), bytes.to_vec());
+    }
+
+    #[test]
+    fn test_hex_error() {
+        assert_eq!(Hex::decode("abc"), Err(HexDecodeError::OddLength));
+        assert_eq!(Hex::decode("0xabc"), Err(HexDecodeError::OddLength));
+        assert_eq!(Hex::decode("abcg"), Err(HexDecodeError::InvalidByte(3, b'g')));
+        assert_eq!(Hex::decode("0x0x"), Err(HexDecodeError::InvalidByte(1, b'x')));
+
+        // usable as a boxed error
+        let err: Box<dyn std::error::Error> = Hex::decode("abcg").unwrap_err().into();
+        assert_eq!(err.to_string(), "invalid hex digit 'g' at offset 3");
+    }
+
+    #[test]
+    fn test_base64url_parse() {
+        let bytes = random_bytes::<32>();
+        let encoded = Base64URL::encode(bytes);
+        let parsed: Base64URL = encoded.parse().unwrap();
+        assert_eq!(*parsed, *encoded);
+        assert_eq!(parsed.to_string(), *encoded);
+        assert_eq!(Base64URL::decode(&*parsed).unwrap(), bytes.to_vec());
+        assert_eq!(*Base64URL::try_from(encoded.as_str()).unwrap(), *encoded);
+
+        // test invalid base64url, including standard Base64 characters
+        assert!("not base64url!".parse::<Base64URL>().is_err());
+        assert!(Base64URL::try_from("ab+/").is_err());
+
+        // test padding, which decode accepts
+        assert_eq!(Base64URL::decode("AQ==").unwrap(), vec![1u8]);
+        assert_eq!("AQ==".parse::<Base64URL>().err(), Some(base64::DecodeError::InvalidByte(2, b'=')));
+        assert!(Base64URL::try_from("AQI=").is_err());
+        assert_eq!(*"AQI".parse::<Base64URL>().unwrap(), "AQI");
+    }
+
+    #[test]
+    fn test_base64url_address_and_hash() {
+        let address: PublicAddress = random_bytes::<32>();
+        let string = Base64URLAddress(address).to_string();
+        assert_eq!(string, *Base64URL::encode(address));
+        assert_eq!(string.parse::<Base64URLAddress>(), Ok(Base64URLAddress(address)));
+
+        let hash: Sha256Hash = random_bytes::<32>();
+        let string = Base64URLHash(hash).to_string();
+        assert_eq!(string, *Base64URL::encode(hash));
+        assert_eq!(string.parse::<Base64URLHash>(), Ok(Base64URLHash(hash)));
+    }
+
+    #[test]
+    fn test_parse_address_list() {
+        let addresses = [random_bytes::<32>(), random_bytes::<32>(), random_bytes::<32>()];
+        let input = format!("{}\n\n  {}  \r\n\t\n{}\n",
+            Base64URLAddress(addresses[0]), Base64URLAddress(addresses[1]), Base64URLAddress(addresses[2]));
+        assert_eq!(crypto::parse_address_list(&input), Ok(addresses.to_vec()));
+        assert_eq!(crypto::parse_address_list(""), Ok(vec![]));
+
+        // test malformed entries, reported by the 1-based line number of the first failure
+        let input = format!("{}\n\nnot base64url!\n{}", Base64URLAddress(addresses[0]), Base64URL::encode([0u8; 31]));
+        assert!(matches!(crypto::parse_address_list(&input), Err((3, ParseBase64URLError::InvalidBase64URL(_)))));
+        let input = format!("{}\n{}", Base64URLAddress(addresses[0]), Base64URL::encode([0u8; 31]));
+        assert_eq!(crypto::parse_address_list(&input), Err((2, ParseBase64URLError::WrongLength(31))));
+    }
+
+    #[test]
+    fn test_base64url_address_and_hash_error() {
+        // test invalid base64url
+        assert!(matches!("not base64url!".parse::<Base64URLAddress>(), Err(ParseBase64URLError::InvalidBase64URL(_))));
+        
+        // test wrong lengths
+        let string = Base64URL::encode(random_bytes::<31>()).to_string();
+        assert_eq!(string.parse::<Base64URLAddress>(), Err(ParseBase64URLError::WrongLength(31)));
+        let string = Base64URL::encode(random_bytes::<64>()).to_string();
+        assert_eq!(string.parse::<Base64URLHash>(), Err(ParseBase64URLError::WrongLength(64)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let block = Block{
+            header: random_blockheader(),
+            transactions: random_transactions(10,10,0, 1024),
+            receipts: random_receipts(10, 10, 5,5,0, 1024),
+        };
+        let json = serde_json::to_string(&block).unwrap();
+        let deserialized: Block = serde_json::from_str(&json).unwrap();
+        assert_block(&block, &deserialized);
+
+        // binary fields are Base64URL strings
+        let tx = &block.transactions[0];
+        let json = serde_json::to_value(tx).unwrap();
+        assert_eq!(json["from_address"], serde_json::Value::String(crate::Base64URL::encode(tx.from_address).to_string()));
+        assert_eq!(json["signature"], serde_json::Value::String(crate::Base64URL::encode(tx.signature).to_string()));
+        assert_eq!(json["value"], serde_json::Value::from(tx.value));
+
+        let spfs = StateProofs {
+            root_hash : random_bytes::<32>(),
+            items : vec![
+                (random_bytes::<21>().to_vec(), Some(random_bytes::<32>().to_vec())), 
+                (random_bytes::<23>().to_vec(), None), 
+            ],
+            proof : vec![random_bytes::<56>().to_vec(), random_bytes::<57>().to_vec()]
+        };
+        let json = serde_json::to_string(&spfs).unwrap();
+        assert_eq!(spfs, serde_json::from_str(&json).unwrap());
+
+        // wrong length of fixed-size field
+        let mut json = serde_json::to_value(tx).unwrap();
+        json["hash"] = serde_json::Value::String(crate::Base64URL::encode([1u8; 31]).to_string());
+        assert!(serde_json::from_value::<Transaction>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_bytes_encodings() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Hashes {
+            #[serde(with = "crate::serde_encodings::bytes_as_hex")]
+            hex: Sha256Hash,
+            #[serde(with = "crate::serde_encodings::bytes_as_base64url")]
+            base64url: Sha256Hash,
+            #[serde(with = "crate::serde_encodings::bytes_as_hex")]
+            hex_list: Vec<Sha256Hash>,
+        }
+
+        let hash = random_bytes::<32>();
+        let hashes = Hashes { hex: hash, base64url: hash, hex_list: vec![hash, [0u8; 32]] };
+        let json = serde_json::to_value(&hashes).unwrap();
+        assert_eq!(json["hex"], serde_json::Value::String(Hex::encode(hash).to_string()));
+        assert_eq!(json["base64url"], serde_json::Value::String(Base64URL::encode(hash).to_string()));
+        assert_eq!(json["hex_list"][1], serde_json::Value::String("00".repeat(32)));
+        assert_eq!(serde_json::from_value::<Hashes>(json.clone()).unwrap(), hashes);
+
+        // hex with 0x prefix
+        let mut prefixed_json = json.clone();
+        prefixed_json["hex"] = serde_json::Value::String(format!("0x{}", *Hex::encode(hash)));
+        assert_eq!(serde_json::from_value::<Hashes>(prefixed_json).unwrap(), hashes);
+
+        // invalid hex
+        let mut invalid_json = json;
+        invalid_json["hex"] = serde_json::Value::String("zz".repeat(32));
+        assert!(serde_json::from_value::<Hashes>(invalid_json).is_err());
     }
 
     fn assert_block(block: &Block, deserialized: &Block) {
@@ -774,6 +3141,20 @@ mod test {
         assert_eq!(transaction.signature, deserialized.signature);
     }
 
+    type StateTrieHasher = <NoExtensionLayout as TrieLayout>::Hash;
+
+    fn state_trie(entries: &[(Vec<u8>, Vec<u8>)]) -> (MemoryDB<StateTrieHasher, HashKey<StateTrieHasher>, DBValue>, Sha256Hash) {
+        let mut db = MemoryDB::<StateTrieHasher, HashKey<StateTrieHasher>, DBValue>::default();
+        let mut root_hash = Sha256Hash::default();
+        {
+            let mut trie = TrieDBMutBuilder::<NoExtensionLayout>::new(&mut db, &mut root_hash).build();
+            for (key, value) in entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+        (db, root_hash)
+    }
+
     fn random_bytes<const N: usize>() -> [u8; N] {
         let mut res = [0u8; N];
         for i in 0..N {
@@ -812,6 +3193,17 @@ mod test {
         }
     }
 
+    fn blockheader_meta(header: &BlockHeader) -> BlockHeaderMeta {
+        BlockHeaderMeta {
+            app_id: header.app_id,
+            height: header.height,
+            justify: header.justify.clone(),
+            version_number: header.version_number,
+            timestamp: header.timestamp,
+            state_hash: header.state_hash,
+        }
+    }
+
     fn random_transaction(min_data_size :usize, max_data_size :usize) -> Transaction {
         let data_size = {
             let rand_size = max_data_size - min_data_size;
@@ -831,6 +3223,17 @@ mod test {
         }
     }
 
+    fn signed_transaction(keypair: &ed25519_dalek::Keypair) -> Transaction {
+        let mut tx = Transaction {
+            from_address: keypair.public.to_bytes(),
+            hash: [0u8; 32],
+            signature: [0u8; 64],
+            ..random_transaction(0, 1024)
+        };
+        tx.sign(keypair);
+        tx
+    }
+
     fn random_event(min_data_size :usize, max_data_size :usize) -> Event {
         let topic_data_size = {
             let rand_size = max_data_size - min_data_size;
@@ -14,40 +14,196 @@
  limitations under the License.
  */
 
+use rs_merkle::{MerkleTree, algorithms::Sha256};
+use trie_db::{Hasher, TrieLayout};
+use trie_db::proof::{verify_proof, VerifyError};
 use crate::{crypto, Serializable, Deserializable};
 
 /// MerfleProof defines fields required in proving leaves hashes given a root hash and other related information
 /// The fields are compatible to function `verify` used in crate [rs_merkle](https://docs.rs/rs_merkle/latest/rs_merkle/).
 #[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MerkleProof {
     /// Merkle root hash required in the proof
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub root_hash: crypto::Sha256Hash,
     /// Number of Leaves in the Merkle Tree
     pub total_leaves_count: usize,
     /// Vector of u32 integers. Integer li[i] represents the i-th leave to prove in the Trie
     pub leaf_indices: Vec<usize>,
     /// Vector of sha256 hashes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub leaf_hashes: Vec<crypto::Sha256Hash>,
     /// Bytes used for verification
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub proof: Vec<u8>,
 }
 
+impl MerkleProof {
+    /// Build a proof that the leaves at `indices_to_prove` are in the Merkle tree over `leaves`.
+    /// 
+    /// # Panics
+    /// Panics if any of `indices_to_prove` is out of range of `leaves`.
+    pub fn build(leaves: &[crypto::Sha256Hash], indices_to_prove: &[usize]) -> MerkleProof {
+        let merkle_tree = MerkleTree::<Sha256>::from_leaves(leaves);
+        MerkleProof {
+            // As in crypto::merkle_root, the root of an empty tree is all 0s.
+            root_hash: merkle_tree.root().unwrap_or([0; 32]),
+            total_leaves_count: leaves.len(),
+            leaf_indices: indices_to_prove.to_vec(),
+            leaf_hashes: indices_to_prove.iter().map(|i| leaves[*i]).collect(),
+            proof: merkle_tree.proof(indices_to_prove).to_bytes(),
+        }
+    }
+
+    /// Size of this MerkleProof when serialized, computed without serializing it.
+    pub fn size_of(&self) -> usize {
+        32 + 8 + (4 + 8 * self.leaf_indices.len()) + (4 + 32 * self.leaf_hashes.len()) + (4 + self.proof.len())
+    }
+
+    /// Serialize this MerkleProof into the start of `buf`, returning the number of bytes written, i.e., [MerkleProof::size_of].
+    /// This lets a container of proofs serialize them into one shared buffer without allocating for each proof.
+    /// 
+    /// # Panics
+    /// Panics if `buf` is shorter than [MerkleProof::size_of].
+    pub fn serialize_to_slice(&self, buf: &mut [u8]) -> usize {
+        let size = self.size_of();
+        MerkleProof::serialize_into(self, &mut &mut buf[..size]).unwrap();
+        size
+    }
+
+    /// Verify that `leaf_hashes` are the leaves at `leaf_indices` of the Merkle tree with root `root_hash`, using `proof`.
+    /// Returns false if `proof` cannot be decoded.
+    pub fn verify(&self) -> bool {
+        match rs_merkle::MerkleProof::<Sha256>::from_bytes(&self.proof) {
+            Ok(proof) => proof.verify(self.root_hash, &self.leaf_indices, &self.leaf_hashes, self.total_leaves_count),
+            Err(_) => false,
+        }
+    }
+}
+
 /// StateProof is sequence of subset of nodes in trie traversed in pre-order traversal order.
 pub type StateProof = Vec<Vec<u8>>;
 /// StateProofItem contains key-value pair to verify with StateProof
 pub type StateProofItem = (Vec<u8>, Option<Vec<u8>>);
 
 /// StateProofs is compatible to functions in crate [trie-db](https://docs.rs/trie-db/latest/trie_db/)
+/// 
+/// The format of the trie nodes in `proof` is that of the world-state trie, so it is verified with the [TrieLayout] (hasher
+/// and node codec) of that trie, which is supplied by the caller.
 #[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StateProofs {
     /// Merkle root hash required in the proof
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub root_hash :crypto::Sha256Hash,
     /// Items are key-value pairs to verify with root hash and proof. 
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub items : Vec<StateProofItem>,
     /// Proof is sequence of some nodes in trie traversed in pre-order traversal order
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub proof : StateProof
 }
 
+impl StateProofs {
+    /// Verify `items` against `root_hash` using `proof`, whose nodes are in the format of trie layout `L`. An item with value
+    /// `None` asserts that its key is absent from the trie.
+    /// 
+    /// Returns `Ok(false)` if the proof is well-formed but does not agree with the items (a value does not match, or a key asserted absent
+    /// is present), and an error if the proof itself is malformed.
+    pub fn verify<L>(&self) -> Result<bool, StateProofError>
+    where L: TrieLayout, L::Hash: Hasher<Out = crypto::Sha256Hash> {
+        Self::verify_with_root::<L>(&self.root_hash, &self.proof, &self.items)
+    }
+
+    /// Verify `items` against `root`, an externally-supplied root hash (e.g., the state hash of a particular block), using `proof`.
+    /// 
+    /// Returns an error if `root` conflicts with the embedded `root_hash`. Otherwise, behaves as [StateProofs::verify].
+    pub fn verify_against<L>(&self, root: &crypto::Sha256Hash) -> Result<bool, StateProofError>
+    where L: TrieLayout, L::Hash: Hasher<Out = crypto::Sha256Hash> {
+        if *root != self.root_hash {
+            return Err(StateProofError::ConflictingRootHash(*root))
+        }
+        Self::verify_with_root::<L>(root, &self.proof, &self.items)
+    }
+
+    /// Size of this StateProofs when serialized, computed without serializing it.
+    pub fn size_of(&self) -> usize {
+        let items_size: usize = self.items.iter()
+            .map(|(key, value)| (4 + key.len()) + 1 + value.as_ref().map_or(0, |value| 4 + value.len()))
+            .sum();
+        let proof_size: usize = self.proof.iter().map(|node| 4 + node.len()).sum();
+        32 + (4 + items_size) + (4 + proof_size)
+    }
+
+    /// Serialize this StateProofs into the start of `buf`, returning the number of bytes written, i.e., [StateProofs::size_of].
+    /// See [MerkleProof::serialize_to_slice].
+    /// 
+    /// # Panics
+    /// Panics if `buf` is shorter than [StateProofs::size_of].
+    pub fn serialize_to_slice(&self, buf: &mut [u8]) -> usize {
+        let size = self.size_of();
+        StateProofs::serialize_into(self, &mut &mut buf[..size]).unwrap();
+        size
+    }
+
+    /// Statistics on the size of this proof, e.g., to monitor for trie bloat.
+    pub fn proof_stats(&self) -> ProofStats {
+        ProofStats {
+            node_count: self.proof.len(),
+            total_bytes: self.proof.iter().map(Vec::len).sum(),
+            item_count: self.items.len(),
+        }
+    }
+
+    fn verify_with_root<L>(root: &crypto::Sha256Hash, proof: &StateProof, items: &[StateProofItem]) -> Result<bool, StateProofError>
+    where L: TrieLayout, L::Hash: Hasher<Out = crypto::Sha256Hash> {
+        match verify_proof::<L, _, _, _>(root, proof, items.iter()) {
+            Ok(()) => Ok(true),
+            Err(VerifyError::ValueMismatch(_)) | Err(VerifyError::RootMismatch(_)) => Ok(false),
+            Err(VerifyError::DuplicateKey(key)) => Err(StateProofError::DuplicateKey(key)),
+            Err(VerifyError::ExtraneousNode) => Err(StateProofError::ExtraneousNode),
+            Err(VerifyError::ExtraneousValue(key)) => Err(StateProofError::ExtraneousValue(key)),
+            Err(VerifyError::ExtraneousHashReference(hash)) => Err(StateProofError::ExtraneousHashReference(hash)),
+            Err(VerifyError::InvalidChildReference(data)) => Err(StateProofError::InvalidChildReference(data)),
+            Err(VerifyError::IncompleteProof) => Err(StateProofError::IncompleteProof),
+            Err(VerifyError::DecodeError(_)) => Err(StateProofError::UndecodableNode),
+        }
+    }
+}
+
+/// ProofStats describes the size of a [StateProofs]. See [StateProofs::proof_stats].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProofStats {
+    /// Number of trie nodes in `proof`
+    pub node_count: usize,
+    /// Total size of the trie nodes in `proof`, in bytes
+    pub total_bytes: usize,
+    /// Number of key-value pairs in `items`
+    pub item_count: usize,
+}
+
+/// StateProofError describes why a [StateProofs] could not be verified because its `proof` is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateProofError {
+    /// The same key appears more than once in `items`.
+    DuplicateKey(Vec<u8>),
+    /// The proof contains a node that is not needed to verify the items.
+    ExtraneousNode,
+    /// The proof contains the value of an item's key, which should have been omitted.
+    ExtraneousValue(Vec<u8>),
+    /// The proof contains a hash reference that should have been omitted.
+    ExtraneousHashReference(crypto::Sha256Hash),
+    /// The proof contains a child reference that is longer than a hash.
+    InvalidChildReference(Vec<u8>),
+    /// The proof is missing nodes required to verify the items.
+    IncompleteProof,
+    /// A node in the proof cannot be decoded.
+    UndecodableNode,
+    /// The root hash that the proof is verified against conflicts with the embedded `root_hash`.
+    ConflictingRootHash(crypto::Sha256Hash),
+}
+
 impl Serializable<MerkleProof> for MerkleProof {}
 impl Deserializable<MerkleProof> for MerkleProof {}
 impl Serializable<StateProofs> for StateProofs {}
@@ -16,10 +16,14 @@
 
 use crate::{Serializable, Deserializable};
 use std::convert::TryFrom;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
 
 
 /// ReceiptStatusCode defines the success and error types of receipt.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReceiptStatusCode {
 
     /* Success class. */
@@ -80,16 +84,48 @@ pub enum ReceiptStatusCode {
     Else,
 }
 
+/// ReceiptStatusClass is the category of a [ReceiptStatusCode]. See [ReceiptStatusCode::class].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptStatusClass {
+    /// Successful transaction.
+    Success,
+    /// The transaction failed a check before it could be included in a block.
+    PreInclusionDecision,
+    /// The transaction failed to deploy a contract.
+    Deploy,
+    /// The transaction failed during execution of the entree smart contract.
+    EtoC,
+    /// The transaction failed during execution of an internal transaction.
+    InternalTransaction,
+    /// Other error.
+    Miscellaneous,
+}
+
+/// StatusCodeInfo describes a [ReceiptStatusCode] for documentation and tooling. See [ReceiptStatusCode::describe].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusCodeInfo {
+    /// The byte that the status code is serialized into
+    pub code: u8,
+    /// Name of the status code
+    pub name: &'static str,
+    /// Class which the status code belongs to
+    pub class: ReceiptStatusClass,
+    /// See [ReceiptStatusCode::is_retryable]
+    pub is_retryable: bool,
+    /// See [ReceiptStatusCode::is_includable]
+    pub is_includable: bool,
+}
+
 impl borsh::BorshSerialize for ReceiptStatusCode {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         let c: u8 = self.clone().into();
-        c.serialize(writer)
+        borsh::BorshSerialize::serialize(&c, writer)
     }
 }
 
 impl borsh::BorshDeserialize for ReceiptStatusCode {
     fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
-        match u8::deserialize(buf) {
+        match <u8 as borsh::BorshDeserialize>::deserialize(buf) {
             Ok(b) => {
                 match Self::try_from(b) {
                     Ok(sc) => Ok(sc),
@@ -128,6 +164,43 @@ impl Into<u8> for ReceiptStatusCode {
     }
 }
 
+/// ReceiptStatusCodes are ordered by the byte that they are serialized into (see `Into<u8>`), not by declaration order or by
+/// the severity of the error.
+impl PartialOrd for ReceiptStatusCode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReceiptStatusCode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Into::<u8>::into(self.clone()).cmp(&other.clone().into())
+    }
+}
+
+impl fmt::Display for ReceiptStatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ReceiptStatusCode::Success => "success",
+            ReceiptStatusCode::WrongNonce => "wrong nonce",
+            ReceiptStatusCode::NotEnoughBalanceForGasLimit => "not enough balance to pay for gas limit",
+            ReceiptStatusCode::NotEnoughBalanceForTransfer => "not enough balance to pay for transfer",
+            ReceiptStatusCode::PreExecutionGasExhausted => "gas exhausted before execution",
+            ReceiptStatusCode::DisallowedOpcode => "contract contains disallowed opcodes",
+            ReceiptStatusCode::CannotCompile => "contract cannot be compiled",
+            ReceiptStatusCode::NoExportedContractMethod => "contract does not export the contract method",
+            ReceiptStatusCode::OtherDeployError => "deployment failed",
+            ReceiptStatusCode::ExecutionProperGasExhausted => "gas exhausted during execution",
+            ReceiptStatusCode::RuntimeError => "runtime error during execution",
+            ReceiptStatusCode::InternalExecutionProperGasExhaustion => "gas exhausted during execution of an internal transaction",
+            ReceiptStatusCode::InternalRuntimeError => "runtime error during execution of an internal transaction",
+            ReceiptStatusCode::InternalNotEnoughBalanceForTransfer => "not enough balance to pay for transfer in an internal transaction",
+            ReceiptStatusCode::Else => "other error",
+        };
+        f.write_str(message)
+    }
+}
+
 impl TryFrom<u8> for ReceiptStatusCode {
     type Error = ();
 
@@ -160,6 +233,85 @@ impl TryFrom<u8> for ReceiptStatusCode {
 }
 
 impl ReceiptStatusCode {
+    /// Every ReceiptStatusCode, ordered by the byte that it is serialized into.
+    pub fn all() -> [ReceiptStatusCode; 15] {
+        [
+            ReceiptStatusCode::Success,
+            ReceiptStatusCode::WrongNonce,
+            ReceiptStatusCode::NotEnoughBalanceForGasLimit,
+            ReceiptStatusCode::NotEnoughBalanceForTransfer,
+            ReceiptStatusCode::PreExecutionGasExhausted,
+            ReceiptStatusCode::DisallowedOpcode,
+            ReceiptStatusCode::CannotCompile,
+            ReceiptStatusCode::NoExportedContractMethod,
+            ReceiptStatusCode::OtherDeployError,
+            ReceiptStatusCode::ExecutionProperGasExhausted,
+            ReceiptStatusCode::RuntimeError,
+            ReceiptStatusCode::InternalExecutionProperGasExhaustion,
+            ReceiptStatusCode::InternalRuntimeError,
+            ReceiptStatusCode::InternalNotEnoughBalanceForTransfer,
+            ReceiptStatusCode::Else,
+        ]
+    }
+
+    /// Name of this status code, which is the name of its variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReceiptStatusCode::Success => "Success",
+            ReceiptStatusCode::WrongNonce => "WrongNonce",
+            ReceiptStatusCode::NotEnoughBalanceForGasLimit => "NotEnoughBalanceForGasLimit",
+            ReceiptStatusCode::NotEnoughBalanceForTransfer => "NotEnoughBalanceForTransfer",
+            ReceiptStatusCode::PreExecutionGasExhausted => "PreExecutionGasExhausted",
+            ReceiptStatusCode::DisallowedOpcode => "DisallowedOpcode",
+            ReceiptStatusCode::CannotCompile => "CannotCompile",
+            ReceiptStatusCode::NoExportedContractMethod => "NoExportedContractMethod",
+            ReceiptStatusCode::OtherDeployError => "OtherDeployError",
+            ReceiptStatusCode::ExecutionProperGasExhausted => "ExecutionProperGasExhausted",
+            ReceiptStatusCode::RuntimeError => "RuntimeError",
+            ReceiptStatusCode::InternalExecutionProperGasExhaustion => "InternalExecutionProperGasExhaustion",
+            ReceiptStatusCode::InternalRuntimeError => "InternalRuntimeError",
+            ReceiptStatusCode::InternalNotEnoughBalanceForTransfer => "InternalNotEnoughBalanceForTransfer",
+            ReceiptStatusCode::Else => "Else",
+        }
+    }
+
+    /// Describe this status code, e.g., to generate a table of all status codes.
+    pub fn describe(&self) -> StatusCodeInfo {
+        StatusCodeInfo {
+            code: self.clone().into(),
+            name: self.name(),
+            class: self.class(),
+            is_retryable: self.is_retryable(),
+            is_includable: self.is_includable(),
+        }
+    }
+
+    /// The class which this status code belongs to.
+    pub fn class(&self) -> ReceiptStatusClass {
+        match self {
+            ReceiptStatusCode::Success => ReceiptStatusClass::Success,
+
+            ReceiptStatusCode::WrongNonce
+            | ReceiptStatusCode::NotEnoughBalanceForGasLimit
+            | ReceiptStatusCode::NotEnoughBalanceForTransfer
+            | ReceiptStatusCode::PreExecutionGasExhausted => ReceiptStatusClass::PreInclusionDecision,
+
+            ReceiptStatusCode::DisallowedOpcode
+            | ReceiptStatusCode::CannotCompile
+            | ReceiptStatusCode::NoExportedContractMethod
+            | ReceiptStatusCode::OtherDeployError => ReceiptStatusClass::Deploy,
+
+            ReceiptStatusCode::ExecutionProperGasExhausted
+            | ReceiptStatusCode::RuntimeError => ReceiptStatusClass::EtoC,
+
+            ReceiptStatusCode::InternalExecutionProperGasExhaustion
+            | ReceiptStatusCode::InternalRuntimeError
+            | ReceiptStatusCode::InternalNotEnoughBalanceForTransfer => ReceiptStatusClass::InternalTransaction,
+
+            ReceiptStatusCode::Else => ReceiptStatusClass::Miscellaneous,
+        }
+    }
+
     pub fn is_success(&self) -> bool {
         ReceiptStatusCode::Success == *self
     }
@@ -177,6 +329,20 @@ impl ReceiptStatusCode {
         || ReceiptStatusCode::InternalNotEnoughBalanceForTransfer == *self
     }
 
+    /// Returns true if the transaction failed to deploy a contract, i.e., the status code is in the Deploy class.
+    pub fn is_deploy_error(&self) -> bool {
+        self.class() == ReceiptStatusClass::Deploy
+    }
+
+    /// Returns true if the transaction failed during execution because of a runtime error or gas exhaustion, either of the
+    /// entree smart contract or of an internal transaction.
+    pub fn is_execution_error(&self) -> bool {
+        ReceiptStatusCode::ExecutionProperGasExhausted == *self
+        || ReceiptStatusCode::RuntimeError == *self
+        || ReceiptStatusCode::InternalExecutionProperGasExhaustion == *self
+        || ReceiptStatusCode::InternalRuntimeError == *self
+    }
+
     pub fn is_retryable(&self) -> bool {
         ReceiptStatusCode::WrongNonce == *self 
         || ReceiptStatusCode::NotEnoughBalanceForGasLimit == *self 
@@ -184,5 +350,15 @@ impl ReceiptStatusCode {
     }
 }
 
+/// Count the occurrences of each status code in `codes`, e.g., to monitor the reasons that transactions fail across blocks.
+/// Status codes that do not occur are absent from the result.
+pub fn histogram(codes: impl Iterator<Item = ReceiptStatusCode>) -> BTreeMap<ReceiptStatusCode, u64> {
+    let mut counts = BTreeMap::new();
+    for code in codes {
+        *counts.entry(code).or_insert(0) += 1;
+    }
+    counts
+}
+
 impl Serializable<ReceiptStatusCode> for ReceiptStatusCode {}
 impl Deserializable<ReceiptStatusCode> for ReceiptStatusCode {}
\ No newline at end of file
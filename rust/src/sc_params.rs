@@ -14,50 +14,143 @@
  limitations under the License.
  */
 
-use crate::{crypto, Serializable, Deserializable};
+use crate::{crypto, BlockHeader, DeserializationContext, Transaction, Serializable, Deserializable};
 
 
 /// ParamsFromTransaction defines information that supplies to contract method exection.
 #[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParamsFromTransaction {
     /// From Address of this transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub from_address :crypto::PublicAddress,
     /// To Address of the transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub to_address :crypto::PublicAddress,
     /// Transaction data. Equivalent to "data" in [crate::Transaction]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub data :Vec<u8>,
     /// Transaction value. Equivalent to "value" in [crate::Transaction]
     pub value :u64,
     /// Transaction hash. Equivalent to "hash" in [crate::Transaction]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub transaction_hash :crypto::Sha256Hash
 }
 
+impl From<&Transaction> for ParamsFromTransaction {
+    fn from(txn: &Transaction) -> ParamsFromTransaction {
+        ParamsFromTransaction {
+            from_address: txn.from_address,
+            to_address: txn.to_address,
+            data: txn.data.clone(),
+            value: txn.value,
+            transaction_hash: txn.hash,
+        }
+    }
+}
+
 /// ParamsFromBlockchain defines information that supplies to contract method exection.
 #[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParamsFromBlockchain {
     /// Height of the Block
     pub this_block_number :u64,
     /// Previous Block Hash 
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub prev_block_hash :crypto::Sha256Hash,
     /// Unix timestamp
     pub timestamp :u32,
     /// Random Bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub random_bytes :crypto::Sha256Hash,
 }
 
+impl ParamsFromBlockchain {
+    /// Create the ParamsFromBlockchain of the block with `header`. The previous block hash is the hash of the block justified by
+    /// `header.justify`. `random_bytes` are not part of the header, so they are supplied by the caller.
+    pub fn from_header(header: &BlockHeader, random_bytes: crypto::Sha256Hash) -> ParamsFromBlockchain {
+        ParamsFromBlockchain {
+            this_block_number: header.height,
+            prev_block_hash: header.justify.block_hash,
+            timestamp: header.timestamp,
+            random_bytes,
+        }
+    }
+}
+
 /// CallData defines the data format that passes to entry point of the contact
 /// 
 /// The struct contains data types which are serialized into the field "data" in [crate::Transaction].
-#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallData {
     /// function name of contract with entrypoint methods. Empty string can be used for calling `init` method.
     pub method_name :String,
 
     /// arguments to function (entrypoint method)
-    /// In contract with entrypoint methods, the arguments should be deserialized to vector of Vec<u8> and then pass as function arguments
+    /// In contract with entrypoint methods, the arguments should be deserialized to vector of Vec<u8> and then pass as function arguments.
+    /// See [CallData::from_multiarg] and [CallData::arguments_as_multiarg].
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub arguments :Vec<u8>
 }
 
+impl borsh::BorshDeserialize for CallData {
+    /// Deserialize a CallData, rejecting a `method_name` longer than [CallData::MAX_METHOD_NAME_LENGTH] as soon as its length
+    /// prefix is read, before reading the name itself.
+    /// 
+    /// The offsets in the [DeserializationContext] of an error are relative to the start of the CallData, not to the start of
+    /// any buffer that it is embedded in (e.g., a Transaction's `data`).
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let method_name_len = <u32 as borsh::BorshDeserialize>::deserialize(buf)
+            .map_err(|e| DeserializationContext::wrap(e, "calldata method name length", 0))? as usize;
+        if method_name_len > CallData::MAX_METHOD_NAME_LENGTH {
+            return Err(DeserializationContext::error(std::io::ErrorKind::InvalidData, "calldata method name length", 0, "Method name too long"))
+        }
+        if buf.len() < method_name_len {
+            return Err(DeserializationContext::error(std::io::ErrorKind::UnexpectedEof, "calldata method name", 4, "Unexpected end of method name"))
+        }
+        let (method_name_bs, rest) = buf.split_at(method_name_len);
+        let method_name = String::from_utf8(method_name_bs.to_vec())
+            .map_err(|_| DeserializationContext::error(std::io::ErrorKind::InvalidData, "calldata method name", 4, "Method name is not valid UTF-8"))?;
+        *buf = rest;
+
+        let arguments = <Vec<u8> as borsh::BorshDeserialize>::deserialize(buf)
+            .map_err(|e| DeserializationContext::wrap(e, "calldata arguments", 4 + method_name_len))?;
+        Ok(CallData { method_name, arguments })
+    }
+}
+
+impl CallData {
+    /// Maximum length of `method_name`, in bytes, that a CallData can be deserialized with.
+    /// 
+    /// This is only checked when deserializing. Serializing a CallData, e.g., in [Transaction::with_call_data], does not check it,
+    /// and produces data that cannot be deserialized back into a CallData if `method_name` is longer than this.
+    pub const MAX_METHOD_NAME_LENGTH: usize = 256;
+
+    /// Create a CallData which calls the `init` method of the contract, i.e., with an empty `method_name`.
+    pub fn init(arguments: Vec<u8>) -> CallData {
+        CallData { method_name: String::new(), arguments }
+    }
+
+    /// Create a CallData which calls `method_name` with multiple arguments, each of which is already serialized. `arguments` is set
+    /// to the serialized `Vec<Vec<u8>>` of `args`, i.e., the number of arguments as a little-endian u32, then each argument as its
+    /// length as a little-endian u32 followed by its bytes.
+    pub fn from_multiarg(method_name: String, args: Vec<Vec<u8>>) -> CallData {
+        CallData { method_name, arguments: Vec::<Vec<u8>>::serialize(&args) }
+    }
+
+    /// Deserialize `arguments` into the multiple arguments of a contract with entrypoint methods. The inverse of [CallData::from_multiarg].
+    /// Returns an error if `arguments` is not a serialized `Vec<Vec<u8>>`.
+    pub fn arguments_as_multiarg(&self) -> Result<Vec<Vec<u8>>, std::io::Error> {
+        Vec::<Vec<u8>>::deserialize(&self.arguments)
+    }
+
+    /// Returns true if this CallData calls the `init` method of the contract.
+    pub fn is_init(&self) -> bool {
+        self.method_name.is_empty()
+    }
+}
+
 impl Serializable<ParamsFromTransaction> for ParamsFromTransaction {}
 impl Deserializable<ParamsFromTransaction> for ParamsFromTransaction {}
 impl Serializable<ParamsFromBlockchain> for ParamsFromBlockchain {}
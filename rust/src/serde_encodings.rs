@@ -0,0 +1,185 @@
+/*
+ Copyright 2022 ParallelChain Lab
+
+ Licensed under the Apache License, Version 2.0 (the "License");
+ you may not use this file except in compliance with the License.
+ You may obtain a copy of the License at
+
+     http://www.apache.org/licenses/LICENSE-2.0
+
+ Unless required by applicable law or agreed to in writing, software
+ distributed under the License is distributed on an "AS IS" BASIS,
+ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ See the License for the specific language governing permissions and
+ limitations under the License.
+ */
+
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Error as _, ser::Error as _};
+use crate::{Base64URL, Hex};
+
+/// BytesEncoding is a binary-to-text encoding which binary fields can be encoded with in serde formats.
+pub trait BytesEncoding {
+    fn encode(bytes: &[u8]) -> String;
+    fn decode(text: &str) -> Result<Vec<u8>, String>;
+}
+
+/// Encodes bytes as [Base64URL].
+pub struct Base64URLEncoding;
+
+impl BytesEncoding for Base64URLEncoding {
+    fn encode(bytes: &[u8]) -> String {
+        Base64URL::encode(bytes).to_string()
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, String> {
+        Base64URL::decode(text).map_err(|e| e.to_string())
+    }
+}
+
+/// Encodes bytes as [Hex].
+pub struct HexEncoding;
+
+impl BytesEncoding for HexEncoding {
+    fn encode(bytes: &[u8]) -> String {
+        Hex::encode(bytes).to_string()
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, String> {
+        Hex::decode(text).map_err(|e| format!("{:?}", e))
+    }
+}
+
+/// BytesField is implemented by the binary field types of the protocol types, which are encoded as strings in serde formats
+/// using a [BytesEncoding]. Containers of such fields (Vec, Option, pairs) encode each of their elements the same way.
+pub trait BytesField: Sized {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+impl BytesField for Vec<u8> {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&E::encode(self))
+    }
+
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        E::decode(&text).map_err(D::Error::custom)
+    }
+}
+
+impl<const N: usize> BytesField for [u8; N] {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&E::encode(self))
+    }
+
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize_bytes::<E, _>(deserializer)?;
+        let len = bytes.len();
+        <[u8; N]>::try_from(bytes).map_err(|_| D::Error::custom(format!("expected {} bytes, found {}", N, len)))
+    }
+}
+
+impl<T: BytesField> BytesField for Vec<T> {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Encoded::<E, T>::new))
+    }
+
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let decoded = Vec::<Decoded<E, T>>::deserialize(deserializer)?;
+        Ok(decoded.into_iter().map(|d| d.0).collect())
+    }
+}
+
+impl<T: BytesField> BytesField for Option<T> {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(t) => serializer.serialize_some(&Encoded::<E, T>::new(t)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let decoded = Option::<Decoded<E, T>>::deserialize(deserializer)?;
+        Ok(decoded.map(|d| d.0))
+    }
+}
+
+impl<T1: BytesField, T2: BytesField> BytesField for (T1, T2) {
+    fn serialize_bytes<E: BytesEncoding, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (Encoded::<E, T1>::new(&self.0), Encoded::<E, T2>::new(&self.1)).serialize(serializer)
+    }
+
+    fn deserialize_bytes<'de, E: BytesEncoding, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (t1, t2) = <(Decoded<E, T1>, Decoded<E, T2>)>::deserialize(deserializer)?;
+        Ok((t1.0, t2.0))
+    }
+}
+
+struct Encoded<'a, E, T>(&'a T, PhantomData<E>);
+
+impl<'a, E, T> Encoded<'a, E, T> {
+    fn new(t: &'a T) -> Self {
+        Encoded(t, PhantomData)
+    }
+}
+
+impl<'a, E: BytesEncoding, T: BytesField> Serialize for Encoded<'a, E, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_bytes::<E, S>(serializer)
+    }
+}
+
+struct Decoded<E, T>(T, PhantomData<E>);
+
+impl<'de, E: BytesEncoding, T: BytesField> Deserialize<'de> for Decoded<E, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_bytes::<E, D>(deserializer).map(|t| Decoded(t, PhantomData))
+    }
+}
+
+/// Use with `#[serde(with = "bytes_as_base64url")]` on fields whose type implements [BytesField] to encode them as Base64URL strings.
+pub mod bytes_as_base64url {
+    use serde::{Serializer, Deserializer};
+    use super::{BytesField, Base64URLEncoding};
+
+    pub fn serialize<T: BytesField, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_bytes::<Base64URLEncoding, S>(serializer)
+    }
+
+    pub fn deserialize<'de, T: BytesField, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_bytes::<Base64URLEncoding, D>(deserializer)
+    }
+}
+
+/// Use with `#[serde(with = "bytes_as_hex")]` on fields whose type implements [BytesField] to encode them as Hex strings.
+pub mod bytes_as_hex {
+    use serde::{Serializer, Deserializer};
+    use super::{BytesField, HexEncoding};
+
+    pub fn serialize<T: BytesField, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_bytes::<HexEncoding, S>(serializer)
+    }
+
+    pub fn deserialize<'de, T: BytesField, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_bytes::<HexEncoding, D>(deserializer)
+    }
+}
+
+/// Use with `#[serde(with = "borsh_as_base64url")]` on fields of types defined outside of this crate (e.g., in hotstuff_rs_types)
+/// which do not implement serde traits. The field is encoded as the Base64URL string of its Borsh serialization.
+pub mod borsh_as_base64url {
+    use serde::{Serializer, Deserializer};
+    use super::*;
+
+    pub fn serialize<T: borsh::BorshSerialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = value.try_to_vec().map_err(S::Error::custom)?;
+        bytes.serialize_bytes::<Base64URLEncoding, S>(serializer)
+    }
+
+    pub fn deserialize<'de, T: borsh::BorshDeserialize, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let bytes = Vec::<u8>::deserialize_bytes::<Base64URLEncoding, D>(deserializer)?;
+        T::try_from_slice(&bytes).map_err(D::Error::custom)
+    }
+}
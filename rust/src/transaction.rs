@@ -14,18 +14,23 @@
  limitations under the License.
  */
 
-use ed25519_dalek::{PublicKey, Signature, Verifier};
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
 use sha2::{Sha256, Digest};
-use crate::{crypto, receipt_status_codes, Serializable, Deserializable};
+use crate::{crypto, receipt_status_codes, CallData, DeserializationContext, Serializable, Deserializable};
 
 /// Transactions are authenticated, non-repudiable messages produced by external accounts 
 /// to authorize blockchain state transitions, either through token transfer or smart contract
 /// execution.
-#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transaction {
     /// Sender address in this transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub from_address: crypto::PublicAddress,
     /// Receiver address in this transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub to_address: crypto::PublicAddress,
     /// Value for transfer from sender to receiver
     pub value: u64,
@@ -36,34 +41,205 @@ pub struct Transaction {
     /// The value used for balance deduction for gas used
     pub gas_price: u64,
     /// Transaction data
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub data: Vec<u8>,
     /// Nonce. Accumulated number of transactions made by “From address”
     pub n_txs_on_chain_from_address: u64,
     /// Hash computed by hashing "Signature" of this transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub hash: crypto::Sha256Hash,
     /// An Ed25519 Signature on this transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub signature: crypto::Signature,
 }
 
 impl Transaction {
+    /// Create a Transaction that deploys a contract, signed by `keypair`. The contract is deployed by sending the
+    /// Transaction to the zero address, with `data` set to the serialized [DeployTransactionData].
+    pub fn new_deployment(
+        keypair: &Keypair,
+        contract_code: Vec<u8>,
+        contract_init_arguments: Vec<u8>,
+        gas_limit: u64,
+        gas_price: u64,
+        n_txs_on_chain_from_address: u64,
+    ) -> Transaction {
+        let deploy_transaction_data = DeployTransactionData { contract_code, contract_init_arguments };
+        let mut txn = Transaction {
+            from_address: keypair.public.to_bytes(),
+            to_address: [0; 32],
+            value: 0,
+            tip: 0,
+            gas_limit,
+            gas_price,
+            data: DeployTransactionData::serialize(&deploy_transaction_data),
+            n_txs_on_chain_from_address,
+            hash: [0; 32],
+            signature: [0; 64],
+        };
+        txn.sign(keypair);
+        txn
+    }
+
+    /// Create an unsigned Transaction that transfers `value` to `to`, with empty `data` and no tip. `from_address`, `hash` and
+    /// `signature` are zeroed: complete the Transaction with [Transaction::sign].
+    pub fn transfer(to: crypto::PublicAddress, value: u64, gas_limit: u64, gas_price: u64, nonce: u64) -> Transaction {
+        Transaction {
+            from_address: [0; 32],
+            to_address: to,
+            value,
+            tip: 0,
+            gas_limit,
+            gas_price,
+            data: Vec::new(),
+            n_txs_on_chain_from_address: nonce,
+            hash: [0; 32],
+            signature: [0; 64],
+        }
+    }
+
+    /// This Transaction with `hash` and `signature` zeroed, e.g., to change its fields (such as to bump its fee) and then sign it
+    /// again with [Transaction::sign].
+    pub fn unsigned(self) -> Transaction {
+        Transaction { hash: [0; 32], signature: [0; 64], ..self }
+    }
+
+    /// A key identifying this Transaction for deduplication in hash-based collections, i.e., its `hash`. This is cheaper
+    /// to hash than the whole Transaction, whose `data` can be large.
+    pub fn dedup_key(&self) -> crypto::Sha256Hash {
+        self.hash
+    }
+
+    /// Classify the intent of this Transaction. The rules are applied in order:
+    /// 1. If `data` is empty, it is a [TransactionKind::Transfer].
+    /// 2. If `to_address` is the zero address and `data` deserializes as [DeployTransactionData], it is a [TransactionKind::Deploy].
+    /// 3. Otherwise, it is a [TransactionKind::Call].
+    pub fn kind(&self) -> TransactionKind {
+        if self.data.is_empty() {
+            TransactionKind::Transfer
+        } else if self.to_address == [0; 32] && self.as_deployment_data().is_ok() {
+            TransactionKind::Deploy
+        } else {
+            TransactionKind::Call
+        }
+    }
+
+    /// Deserialize the `data` of this Transaction as [DeployTransactionData].
+    pub fn as_deployment_data(&self) -> Result<DeployTransactionData, std::io::Error> {
+        DeployTransactionData::deserialize(&self.data)
+    }
+
+    /// Create a Transaction that calls a method of the contract at `to_address`, signed by `keypair`. `data` is set to the
+    /// serialized `call_data`. Use [CallData::init] to call the `init` method. `call_data.method_name` is not checked against
+    /// [CallData::MAX_METHOD_NAME_LENGTH], so check it beforehand, or [Transaction::as_call_data] cannot read the data back.
+    pub fn with_call_data(
+        keypair: &Keypair,
+        to_address: crypto::PublicAddress,
+        value: u64,
+        gas_limit: u64,
+        gas_price: u64,
+        n_txs_on_chain_from_address: u64,
+        call_data: &CallData,
+    ) -> Transaction {
+        let mut txn = Transaction {
+            from_address: keypair.public.to_bytes(),
+            to_address,
+            value,
+            tip: 0,
+            gas_limit,
+            gas_price,
+            data: CallData::serialize(call_data),
+            n_txs_on_chain_from_address,
+            hash: [0; 32],
+            signature: [0; 64],
+        };
+        txn.sign(keypair);
+        txn
+    }
+
+    /// Deserialize the `data` of this Transaction as [CallData]. An empty `method_name` denotes a call to the
+    /// `init` method (see [CallData::is_init]).
+    pub fn as_call_data(&self) -> Result<CallData, std::io::Error> {
+        CallData::deserialize(&self.data)
+    }
+
+    /// Size of the fields that precede `data` in a serialized Transaction.
+    const SIZE_BEFORE_DATA: usize = 32 + 32 + 8 + 8 + 8 + 8;
+    /// Size of the fields that follow `data` in a serialized Transaction.
+    const SIZE_AFTER_DATA: usize = 8 + 32 + 64;
+
+    /// Size of this Transaction when serialized, computed without serializing it.
+    pub fn size_of(&self) -> usize {
+        Self::SIZE_BEFORE_DATA + (4 + self.data.len()) + Self::SIZE_AFTER_DATA
+    }
+
+    /// The minimum gas that this Transaction consumes, before execution, for its inclusion in a block. This is `gas_per_byte`
+    /// times [Transaction::size_of], saturating at `u64::MAX`. The per-byte cost is set by the node, so it is supplied by the caller.
+    pub fn base_gas_cost(&self, gas_per_byte: u64) -> u64 {
+        (self.size_of() as u64).saturating_mul(gas_per_byte)
+    }
+
+    /// The fee actually paid for this Transaction after its execution produced `receipt`: `gas_consumed * gas_price + tip`.
+    /// Returns None if the computation overflows.
+    pub fn actual_fee(&self, receipt: &Receipt) -> Option<u64> {
+        receipt.gas_consumed.checked_mul(self.gas_price)?.checked_add(self.tip)
+    }
+
+    /// Size of the serialized Transaction at the start of `buf`, read from its `data` length prefix without deserializing it.
+    /// Returns an error if `buf` is too short to contain the whole Transaction.
+    pub fn size_from_slice(buf: &[u8]) -> Result<usize, std::io::Error> {
+        let unexpected_eof = |context, offset| DeserializationContext::error(std::io::ErrorKind::UnexpectedEof, context, offset, "Unexpected end of Transaction");
+        let len_bs = buf.get(Self::SIZE_BEFORE_DATA..Self::SIZE_BEFORE_DATA + 4)
+            .ok_or_else(|| unexpected_eof("transaction data length", Self::SIZE_BEFORE_DATA))?;
+        let data_len = u32::from_le_bytes(len_bs.try_into().unwrap()) as usize;
+        let size = Self::SIZE_BEFORE_DATA + 4 + data_len + Self::SIZE_AFTER_DATA;
+        if buf.len() < size {
+            return Err(if buf.len() < Self::SIZE_BEFORE_DATA + 4 + data_len {
+                unexpected_eof("transaction data", Self::SIZE_BEFORE_DATA + 4)
+            } else {
+                unexpected_eof("transaction nonce, hash and signature", Self::SIZE_BEFORE_DATA + 4 + data_len)
+            })
+        }
+        Ok(size)
+    }
+
+    /// The bytes signed by `signature`: the serialized Transaction with `hash` and `signature` set to zeros.
+    /// 
+    /// The fields are serialized in place, so `data` is not cloned.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.size_of());
+        let write_fields = |buf: &mut Vec<u8>| -> std::io::Result<()> {
+            borsh::BorshSerialize::serialize(&self.from_address, buf)?;
+            borsh::BorshSerialize::serialize(&self.to_address, buf)?;
+            borsh::BorshSerialize::serialize(&self.value, buf)?;
+            borsh::BorshSerialize::serialize(&self.tip, buf)?;
+            borsh::BorshSerialize::serialize(&self.gas_limit, buf)?;
+            borsh::BorshSerialize::serialize(&self.gas_price, buf)?;
+            borsh::BorshSerialize::serialize(&self.data, buf)?;
+            borsh::BorshSerialize::serialize(&self.n_txs_on_chain_from_address, buf)?;
+            buf.extend_from_slice(&[0; 32]);
+            buf.extend_from_slice(&[0; 64]);
+            Ok(())
+        };
+        write_fields(&mut buf).unwrap();
+        buf
+    }
+
+    /// Sign this Transaction with `keypair`, setting its `signature` and then its `hash`.
+    /// 
+    /// `from_address` is set to the public key of `keypair` before signing, overriding any prior value, so that the
+    /// signature is always verifiable against `from_address`.
+    pub fn sign(&mut self, keypair: &Keypair) {
+        self.from_address = keypair.public.to_bytes();
+        self.signature = keypair.sign(&self.signable_bytes()).to_bytes();
+        let mut hasher = Sha256::new();
+        hasher.update(self.signature);
+        self.hash = hasher.finalize().into();
+    }
+
     pub fn verify_cryptographic_correctness(&self) -> Result<(), CryptographicallyIncorrectTransactionError> {
         // Verify the signature using the from_address (public key).
-        let signed_msg = {
-            let intermediate_txn = Transaction {
-                from_address: self.from_address.to_owned(),
-                to_address: self.to_address.to_owned(),
-                value: self.value,
-                tip: self.tip,
-                gas_limit: self.gas_limit,
-                gas_price: self.gas_price,
-                data: self.data.to_owned(),
-                n_txs_on_chain_from_address: self.n_txs_on_chain_from_address,
-                hash: [0; 32],
-                signature: [0; 64],
-            };
-
-            Transaction::serialize(&intermediate_txn)
-        };
+        let signed_msg = self.signable_bytes();
         let public_key = PublicKey::from_bytes(&self.from_address)
             .map_err(|_| CryptographicallyIncorrectTransactionError::InvalidFromAddress)?;
         let signature = Signature::from_bytes(&self.signature)
@@ -71,17 +247,42 @@ impl Transaction {
         let _ = public_key.verify(&signed_msg, &signature).map_err(|_| CryptographicallyIncorrectTransactionError::WrongSignature)?;
 
         // Verify the hash over the signature.
-        let mut hasher = Sha256::new();
-        hasher.update(&signature);
-        if self.hash != Into::<crate::Sha256Hash>::into(hasher.finalize()) {
+        if !self.verify_hash() {
             Err(CryptographicallyIncorrectTransactionError::WrongHash)
         } else {
             Ok(())
         }
 
     }
+
+    /// Check that `hash` is the SHA256 hash over `signature`, without verifying the signature itself.
+    pub fn verify_hash(&self) -> bool {
+        let mut hasher = Sha256::new();
+        hasher.update(self.signature);
+        crypto::ct_eq(&self.hash, &hasher.finalize())
+    }
+
+    /// Deserialize a Transaction from `buf`, then check that it is cryptographically correct 
+    /// (see [Transaction::verify_cryptographic_correctness]).
+    pub fn deserialize_and_verify(buf: &[u8]) -> Result<Transaction, TxIngestError> {
+        let txn = Transaction::deserialize(buf).map_err(TxIngestError::WronglySerialized)?;
+        txn.verify_cryptographic_correctness().map_err(TxIngestError::CryptographicallyIncorrect)?;
+        Ok(txn)
+    }
 }
 
+/// TransactionKind is the intent of a [Transaction]. See [Transaction::kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    /// Transfer of `value` without data.
+    Transfer,
+    /// Call of a method of the contract at `to_address`.
+    Call,
+    /// Deployment of a contract.
+    Deploy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CryptographicallyIncorrectTransactionError {
     InvalidFromAddress,
     InvalidSignature,
@@ -89,41 +290,226 @@ pub enum CryptographicallyIncorrectTransactionError {
     WrongHash,
 }
 
+impl std::fmt::Display for CryptographicallyIncorrectTransactionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptographicallyIncorrectTransactionError::InvalidFromAddress => write!(f, "from_address is not a valid Ed25519 public key"),
+            CryptographicallyIncorrectTransactionError::InvalidSignature => write!(f, "signature is not a valid Ed25519 signature"),
+            CryptographicallyIncorrectTransactionError::WrongSignature => write!(f, "signature does not verify against from_address"),
+            CryptographicallyIncorrectTransactionError::WrongHash => write!(f, "hash is not the SHA256 hash of signature"),
+        }
+    }
+}
+
+impl std::error::Error for CryptographicallyIncorrectTransactionError {}
+
+/// TxIngestError describes why a sequence of bytes cannot be accepted as a [Transaction].
+#[derive(Debug)]
+pub enum TxIngestError {
+    /// The bytes are not a valid serialization of a Transaction.
+    WronglySerialized(std::io::Error),
+    /// The Transaction is well-formed, but its signature or hash is incorrect.
+    CryptographicallyIncorrect(CryptographicallyIncorrectTransactionError),
+}
+
+/// TransactionSummary is a compact announcement of a [Transaction], gossiped between mempools before the full
+/// Transaction is fetched.
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionSummary {
+    /// Hash of the transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
+    pub hash: crypto::Sha256Hash,
+    /// Gas price of the transaction
+    pub gas_price: u64,
+    /// Tip of the transaction
+    pub tip: u64,
+    /// Nonce of the transaction. Equivalent to "n_txs_on_chain_from_address" in [Transaction]
+    pub nonce: u64,
+    /// Sender address of the transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
+    pub from_address: crypto::PublicAddress,
+}
+
+impl From<&Transaction> for TransactionSummary {
+    fn from(txn: &Transaction) -> TransactionSummary {
+        TransactionSummary {
+            hash: txn.hash,
+            gas_price: txn.gas_price,
+            tip: txn.tip,
+            nonce: txn.n_txs_on_chain_from_address,
+            from_address: txn.from_address,
+        }
+    }
+}
+
 /// Information that is required in transaction of contract
 /// deployment. It is serialized into the field "data" of [Transaction]. 
 #[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeployTransactionData {
     /// Contract wasm bytecode
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub contract_code: Vec<u8>,
     /// Arguments to "init" method on the deploying contract. Equivalent to field "arguments" in [crate::CallData]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub contract_init_arguments: Vec<u8>
 }
 
+#[cfg(feature = "compression")]
+impl DeployTransactionData {
+    /// Serialize into a format for storage in which `contract_code` is compressed with DEFLATE. The format is the same as
+    /// that of [Serializable::serialize], except that `contract_code` is replaced by its compressed bytes.
+    ///
+    /// This format is not used on the wire: the `data` of a [Transaction] is always the uncompressed serialization.
+    pub fn serialize_compressed(&self) -> Result<Vec<u8>, std::io::Error> {
+        use std::io::Write;
+        let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&self.contract_code)?;
+        let compressed = DeployTransactionData {
+            contract_code: encoder.finish()?,
+            contract_init_arguments: self.contract_init_arguments.clone(),
+        };
+        DeployTransactionData::try_serialize(&compressed)
+    }
+
+    /// Deserialize from the format produced by [DeployTransactionData::serialize_compressed].
+    pub fn deserialize_compressed(buf: &[u8]) -> Result<DeployTransactionData, std::io::Error> {
+        use std::io::Read;
+        let mut compressed = DeployTransactionData::deserialize(buf)?;
+        let mut contract_code = Vec::new();
+        flate2::read::DeflateDecoder::new(compressed.contract_code.as_slice()).read_to_end(&mut contract_code)?;
+        compressed.contract_code = contract_code;
+        Ok(compressed)
+    }
+}
+
 /// Events are messages produced by smart contract executions that are persisted on the blockchain
 /// in a cryptographically-provable way. Events produced by transactions that call smart contracts
-/// are stored in the `events` field of a Block in the order in which they are emitted.
-#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+/// are stored in the `events` field of the transaction's [Receipt] in the order in which they are emitted. See
+/// [crate::Block::iter_events] to iterate over all events in a Block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event { 
     /// Key of this event. It is created from contract execution
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub topic: Vec<u8>,
     /// Value of this event. It is created from contract execution
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub value: Vec<u8>,
 }
 
 /// Receipt defines the result of transaction execution.
-#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Receipt {
     /// Receipt Status code
     pub status_code: receipt_status_codes::ReceiptStatusCode,
     /// Gas consumed for transaction execution
     pub gas_consumed: u64,
     /// Return value from transaction execution
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_encodings::bytes_as_base64url"))]
     pub return_value: Vec<u8>,
     /// Vector of Event
     pub events: Vec<Event>,
 }
 
+impl Event {
+    /// Size of the smallest serialized Event, i.e., one with an empty `topic` and `value`.
+    const MIN_SIZE: usize = 4 + 4;
+
+    /// Size of this Event when serialized, computed without serializing it.
+    pub fn size_of(&self) -> usize {
+        (4 + self.topic.len()) + (4 + self.value.len())
+    }
+
+    /// Deserialize the Event at the start of `buf` without copying its `topic` and `value`, returning a view that borrows them
+    /// from `buf` together with the number of bytes that the Event takes up. Bytes following the Event are ignored, so a
+    /// sequence of serialized Events can be scanned by advancing `buf` by the returned size.
+    pub fn deserialize_ref(buf: &[u8]) -> Result<(EventRef<'_>, usize), std::io::Error> {
+        let mut rest = buf;
+        let topic = read_length_prefixed_slice(&mut rest).map_err(|e| DeserializationContext::wrap(e, "event topic", 0))?;
+        let value_offset = buf.len() - rest.len();
+        let value = read_length_prefixed_slice(&mut rest).map_err(|e| DeserializationContext::wrap(e, "event value", value_offset))?;
+        Ok((EventRef { topic, value }, buf.len() - rest.len()))
+    }
+
+    /// Serialize this Event, prefixed with the byte [SERIALIZATION_VERSION].
+    pub fn serialize_versioned(&self) -> Vec<u8> {
+        serialize_versioned(self)
+    }
+
+    /// Deserialize an Event serialized by [Event::serialize_versioned]. Returns an error if the version byte is not
+    /// [SERIALIZATION_VERSION].
+    pub fn deserialize_versioned(buf: &[u8]) -> Result<Event, std::io::Error> {
+        deserialize_versioned(buf)
+    }
+}
+
+/// EventFilter selects events by topic. See [EventFilter::matches] and [crate::Block::filter_events].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// If set (and `exact_topic` is not), match events whose topic starts with these bytes
+    pub topic_prefix: Option<Vec<u8>>,
+    /// If set, match only events with exactly this topic
+    pub exact_topic: Option<Vec<u8>>,
+}
+
+impl EventFilter {
+    /// Returns true if `event` is selected by this filter. If `exact_topic` is set, the event's topic must equal it, and
+    /// `topic_prefix` is ignored. Otherwise, if `topic_prefix` is set, the event's topic must start with it. A filter with
+    /// neither set matches every event.
+    pub fn matches(&self, event: &Event) -> bool {
+        match (&self.exact_topic, &self.topic_prefix) {
+            (Some(exact_topic), _) => event.topic == *exact_topic,
+            (None, Some(topic_prefix)) => event.topic.starts_with(topic_prefix),
+            (None, None) => true,
+        }
+    }
+}
+
+/// EventRef is a view of a serialized [Event] which borrows its `topic` and `value`. See [Event::deserialize_ref].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventRef<'a> {
+    /// Key of the event. See [Event::topic]
+    pub topic: &'a [u8],
+    /// Value of the event. See [Event::value]
+    pub value: &'a [u8],
+}
+
+impl From<EventRef<'_>> for Event {
+    fn from(event: EventRef<'_>) -> Event {
+        Event { topic: event.topic.to_vec(), value: event.value.to_vec() }
+    }
+}
+
+/// Split a u32 length-prefixed byte string off the start of `buf`, borrowing it rather than copying it.
+fn read_length_prefixed_slice<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], std::io::Error> {
+    let len = <u32 as borsh::BorshDeserialize>::deserialize(buf)? as usize;
+    if buf.len() < len {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected end of length-prefixed bytes"))
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
 impl Receipt {
+    /// Size of this Receipt when serialized, computed without serializing it.
+    pub fn size_of(&self) -> usize {
+        1 + 8 + (4 + self.return_value.len()) + 4 + self.events.iter().map(Event::size_of).sum::<usize>()
+    }
+
+    /// Index the events of this Receipt by topic: each topic maps to the indices in `events` of the events with that topic,
+    /// in ascending order.
+    pub fn build_topic_index(&self) -> BTreeMap<Vec<u8>, Vec<usize>> {
+        let mut index: BTreeMap<Vec<u8>, Vec<usize>> = BTreeMap::new();
+        for (i, event) in self.events.iter().enumerate() {
+            index.entry(event.topic.clone()).or_default().push(i);
+        }
+        index
+    }
+
     pub fn is_success(&self) -> bool {
         self.status_code.is_success()
     }
@@ -135,13 +521,201 @@ impl Receipt {
     pub fn is_retryable(&self) -> bool {
         self.status_code.is_retryable()
     }
+
+    /// Serialize this Receipt, prefixed with the byte [SERIALIZATION_VERSION].
+    pub fn serialize_versioned(&self) -> Vec<u8> {
+        serialize_versioned(self)
+    }
+
+    /// Deserialize a Receipt serialized by [Receipt::serialize_versioned]. Returns an error if the version byte is not
+    /// [SERIALIZATION_VERSION].
+    pub fn deserialize_versioned(buf: &[u8]) -> Result<Receipt, std::io::Error> {
+        deserialize_versioned(buf)
+    }
+
+    /// Deserialize a Receipt from `buf`, keeping as many of its events as can be deserialized. If an event is malformed,
+    /// the returned Receipt holds only the events preceding it, and the error encountered is returned alongside it.
+    /// Bytes following the last event are ignored.
+    /// 
+    /// This is meant for recovery tooling such as best-effort indexers, and MUST NOT be used in consensus: a Receipt returned
+    /// together with an error is not the Receipt that was serialized. If the fields preceding the events cannot be deserialized,
+    /// no Receipt is returned at all.
+    pub fn deserialize_lenient(buf: &[u8]) -> Result<(Receipt, Option<std::io::Error>), std::io::Error> {
+        let mut buf = buf;
+        let status_code = <receipt_status_codes::ReceiptStatusCode as borsh::BorshDeserialize>::deserialize(&mut buf)?;
+        let gas_consumed = <u64 as borsh::BorshDeserialize>::deserialize(&mut buf)?;
+        let return_value = <Vec<u8> as borsh::BorshDeserialize>::deserialize(&mut buf)?;
+
+        let mut receipt = Receipt { status_code, gas_consumed, return_value, events: vec![] };
+        let num_events = match <u32 as borsh::BorshDeserialize>::deserialize(&mut buf) {
+            Ok(num_events) => num_events,
+            Err(e) => return Ok((receipt, Some(e))),
+        };
+        // The remaining bytes cannot hold more events than this, however large `num_events` claims to be.
+        receipt.events.reserve((num_events as usize).min(buf.len() / Event::MIN_SIZE));
+        for _ in 0..num_events {
+            match <Event as borsh::BorshDeserialize>::deserialize(&mut buf) {
+                Ok(event) => receipt.events.push(event),
+                Err(e) => return Ok((receipt, Some(e))),
+            }
+        }
+
+        Ok((receipt, None))
+    }
+}
+
+/// Version of the serialization format of [Receipt] and [Event], prefixed to their versioned serializations.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+fn serialize_versioned<T: borsh::BorshSerialize>(args: &T) -> Vec<u8> {
+    let mut buf = vec![SERIALIZATION_VERSION];
+    args.serialize(&mut buf).unwrap();
+    buf
+}
+
+fn deserialize_versioned<T: borsh::BorshDeserialize>(buf: &[u8]) -> Result<T, std::io::Error> {
+    match buf.split_first() {
+        Some((&SERIALIZATION_VERSION, buf)) => T::try_from_slice(buf),
+        Some((version, _)) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Unknown serialization version {}", version))),
+        None => Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Missing serialization version")),
+    }
+}
+
+/// Write `txn` to `writer` prefixed with its serialized length as a little-endian u32, so that it can be read back with
+/// [read_length_prefixed].
+pub fn write_length_prefixed<W: std::io::Write>(writer: &mut W, txn: &Transaction) -> Result<(), std::io::Error> {
+    let bytes = Transaction::try_serialize(txn)?;
+    let len = u32::try_from(bytes.len()).map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Transaction too large"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&bytes)
+}
+
+/// Read a Transaction written by [write_length_prefixed] from `reader`: a little-endian u32 length, followed by exactly that
+/// many bytes of serialized Transaction.
+pub fn read_length_prefixed<R: std::io::Read>(reader: &mut R) -> Result<Transaction, std::io::Error> {
+    use std::io::Read;
+
+    let mut len_bs = [0u8; 4];
+    reader.read_exact(&mut len_bs)?;
+    let len = u32::from_le_bytes(len_bs) as usize;
+
+    // Read the Transaction without trusting its length prefix for allocation.
+    let mut buf = Vec::new();
+    let n = reader.take(len as u64).read_to_end(&mut buf)?;
+    if n != len {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected end of length-prefixed Transaction"))
+    }
+    Transaction::deserialize(&buf)
+}
+
+/// TransactionStreamReader reads the Transactions of a serialized `Vec<Transaction>` from `reader` one at a time, so that
+/// they can be processed without buffering the whole Vec in memory. The serialization follows the Length-Value pattern: 
+/// the number of Transactions as a little-endian u32, followed by the serialized Transactions.
+pub struct TransactionStreamReader<R: std::io::Read> {
+    reader: R,
+    remaining: Option<u32>,
+}
+
+impl<R: std::io::Read> TransactionStreamReader<R> {
+    pub fn new(reader: R) -> TransactionStreamReader<R> {
+        TransactionStreamReader { reader, remaining: None }
+    }
+
+    /// Number of Transactions that have not been read yet. Returns an error if the length prefix cannot be read.
+    pub fn remaining(&mut self) -> Result<u32, std::io::Error> {
+        match self.remaining {
+            Some(remaining) => Ok(remaining),
+            None => {
+                let mut len_bs = [0u8; 4];
+                self.reader.read_exact(&mut len_bs)?;
+                let remaining = u32::from_le_bytes(len_bs);
+                self.remaining = Some(remaining);
+                Ok(remaining)
+            }
+        }
+    }
+
+    fn read_transaction(&mut self) -> Result<Transaction, std::io::Error> {
+        use std::io::Read;
+
+        let mut buf = vec![0u8; Transaction::SIZE_BEFORE_DATA + 4];
+        self.reader.read_exact(&mut buf)?;
+        let data_len = u32::from_le_bytes(buf[Transaction::SIZE_BEFORE_DATA..].try_into().unwrap()) as usize;
+
+        // Read `data` without trusting its length prefix for allocation.
+        let n = self.reader.by_ref().take(data_len as u64).read_to_end(&mut buf)?;
+        if n != data_len {
+            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected end of Transaction data"))
+        }
+
+        let start = buf.len();
+        buf.resize(start + Transaction::SIZE_AFTER_DATA, 0);
+        self.reader.read_exact(&mut buf[start..])?;
+
+        Transaction::deserialize(&buf)
+    }
+}
+
+impl<R: std::io::Read> Iterator for TransactionStreamReader<R> {
+    type Item = Result<Transaction, std::io::Error>;
+
+    /// Read the next Transaction. Returns None after all Transactions have been read, or after an error has been returned.
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = match self.remaining() {
+            Ok(remaining) => remaining,
+            Err(e) => {
+                self.remaining = Some(0);
+                return Some(Err(e))
+            }
+        };
+        if remaining == 0 {
+            return None
+        }
+
+        match self.read_transaction() {
+            Ok(txn) => {
+                self.remaining = Some(remaining - 1);
+                Some(Ok(txn))
+            },
+            Err(e) => {
+                self.remaining = Some(0);
+                Some(Err(e))
+            }
+        }
+    }
 }
 
 impl Serializable<Transaction> for Transaction {}
 impl Deserializable<Transaction> for Transaction {}
+impl Serializable<TransactionSummary> for TransactionSummary {}
+impl Deserializable<TransactionSummary> for TransactionSummary {}
 impl Serializable<DeployTransactionData> for DeployTransactionData {}
 impl Deserializable<DeployTransactionData> for DeployTransactionData {}
 impl Serializable<Event> for Event {}
 impl Deserializable<Event> for Event {}
 impl Serializable<Receipt> for Receipt {}
-impl Deserializable<Receipt> for Receipt {}
\ No newline at end of file
+impl Deserializable<Receipt> for Receipt {}
+
+impl TryFrom<&[u8]> for Transaction {
+    type Error = std::io::Error;
+
+    fn try_from(buf: &[u8]) -> Result<Transaction, std::io::Error> {
+        Transaction::deserialize(buf)
+    }
+}
+
+impl TryFrom<&[u8]> for Event {
+    type Error = std::io::Error;
+
+    fn try_from(buf: &[u8]) -> Result<Event, std::io::Error> {
+        Event::deserialize(buf)
+    }
+}
+
+impl TryFrom<&[u8]> for Receipt {
+    type Error = std::io::Error;
+
+    fn try_from(buf: &[u8]) -> Result<Receipt, std::io::Error> {
+        Receipt::deserialize(buf)
+    }
+}